@@ -0,0 +1,54 @@
+use dotenv::dotenv;
+use futures::TryStreamExt;
+use std::env;
+use zohohorrorshow::asynchronous;
+use zohohorrorshow::errors::*;
+use zohohorrorshow::prelude::*;
+
+async fn run() -> Result<i32> {
+    dotenv().ok();
+
+    // Authorization may open a browser and wait, so build the client off the runtime's threads.
+    let client = tokio::task::spawn_blocking(|| -> Result<ZohoClient> {
+        ZohoClient::builder(
+            &env::var("ZOHO_CLIENT_ID")?,
+            &env::var("ZOHO_CLIENT_SECRET")?,
+        )
+        .portal(&env::var("ZOHO_PORTAL_NAME")?)
+        .project(&env::var("ZOHO_PROJECT_NAME")?)
+        .build()
+    })
+    .await
+    .expect("building the client panicked")?;
+    let client = asynchronous::ZohoClient::new(client);
+
+    let tickets: Vec<zoho_bug::Bug> = client
+        .project_scope()?
+        .bugs()
+        .stream()
+        .try_collect()
+        .await?;
+    println!("Existing tickets: {}", tickets.len());
+
+    Ok(0)
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the Tokio runtime");
+
+    ::std::process::exit(match runtime.block_on(run()) {
+        Ok(_) => {
+            println!("Goodbye");
+            0
+        }
+        Err(err) => {
+            eprintln!("Error occurred while running: {:?}", err);
+            1
+        }
+    });
+}
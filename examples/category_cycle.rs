@@ -8,7 +8,7 @@ fn run() -> Result<i32> {
     dotenv().ok();
 
     // // Generate the client, with a valid auth token.
-    // let client = ZohoClient::new(&env::var("ZOHO_CLIENT_ID")?, &env::var("ZOHO_CLIENT_SECRET")?)
+    // let client = ZohoClient::builder(&env::var("ZOHO_CLIENT_ID")?, &env::var("ZOHO_CLIENT_SECRET")?)
     //     .set_portal(&env::var("ZOHO_PORTAL_NAME")?)?
     //     .set_project(&env::var("ZOHO_PROJECT_NAME")?)
     //     .chain_err(|| "Could not initialize; exiting")?;
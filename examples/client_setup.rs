@@ -9,12 +9,13 @@ fn run() -> Result<i32> {
     dotenv().ok();
 
     // Generate the client, with a valid auth token.
-    let client = ZohoClient::new(
+    let client = ZohoClient::builder(
         &env::var("ZOHO_CLIENT_ID")?,
         &env::var("ZOHO_CLIENT_SECRET")?,
     )
-    .set_portal(&env::var("ZOHO_PORTAL_NAME")?)?
-    .set_project(&env::var("ZOHO_PROJECT_NAME")?)?;
+    .portal(&env::var("ZOHO_PORTAL_NAME")?)
+    .project(&env::var("ZOHO_PROJECT_NAME")?)
+    .build()?;
 
     // Display the created client
     println!("{:?}", client);
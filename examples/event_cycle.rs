@@ -9,14 +9,16 @@ fn run() -> Result<i32> {
     dotenv().ok();
 
     // Generate the client, with a valid auth token.
-    let client = ZohoClient::new(
+    let client = ZohoClient::builder(
         &env::var("ZOHO_CLIENT_ID")?,
         &env::var("ZOHO_CLIENT_SECRET")?,
     )
-    .set_portal(&env::var("ZOHO_PORTAL_NAME")?)?
-    .set_project(&env::var("ZOHO_PROJECT_NAME")?)?;
+    .portal(&env::var("ZOHO_PORTAL_NAME")?)
+    .project(&env::var("ZOHO_PROJECT_NAME")?)
+    .build()?;
+    let project = client.project_scope()?;
 
-    let mut event = zoho_event::NewEvent {
+    let event = zoho_event::NewEvent {
         title: "TestEvent".to_string(),
         date: "01-01-2020".to_string(),
         hour: "02".to_string(),
@@ -31,16 +33,18 @@ fn run() -> Result<i32> {
         location: None,
     };
 
-    let new_event = &client.events().post(event.clone())?.unwrap().events[0];
+    let new_event = project.events().post(event)?;
     let ne_id = new_event.id;
 
-    event.title = "TestEvent - Updated".to_string();
-    client.event(ne_id).put(event)?;
+    project.event(ne_id).put(zoho_event::UpdateEvent {
+        title: Some("TestEvent - Updated".to_string()),
+        ..Default::default()
+    })?;
 
-    let updated_events = client.events().get()?;
+    let updated_events = project.events().get()?;
     println!("Updated events: {:?}", updated_events);
 
-    let destroyed_event = client.event(ne_id).delete()?;
+    let destroyed_event = project.event(ne_id).delete()?;
     println!("Delete response: {:?}", destroyed_event);
 
     Ok(0)
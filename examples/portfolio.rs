@@ -0,0 +1,47 @@
+use dotenv::dotenv;
+use std::collections::BTreeMap;
+use std::env;
+use zohohorrorshow::errors::*;
+use zohohorrorshow::prelude::*;
+
+fn run() -> Result<i32> {
+    dotenv().ok();
+
+    // Generate the client, with a valid auth token.
+    let client = ZohoClient::builder(
+        &env::var("ZOHO_CLIENT_ID")?,
+        &env::var("ZOHO_CLIENT_SECRET")?,
+    )
+    .portal(&env::var("ZOHO_PORTAL_NAME")?)
+    .build()?;
+
+    // Count open bugs in every project in the portal.
+    let mut open_bugs = BTreeMap::new();
+    for bug in client
+        .portal_scope()?
+        .for_each_project(|project| project.bugs())
+    {
+        let bug = bug?;
+        if !bug.item.closed {
+            *open_bugs.entry(bug.project_name).or_insert(0) += 1;
+        }
+    }
+    println!("Open bugs by project: {:#?}", open_bugs);
+
+    Ok(0)
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    ::std::process::exit(match run() {
+        Ok(_) => {
+            println!("Goodbye");
+            0
+        }
+        Err(err) => {
+            eprintln!("Error occurred while running: {:?}", err);
+            1
+        }
+    });
+}
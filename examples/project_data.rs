@@ -10,14 +10,15 @@ fn run() -> Result<i32> {
     dotenv().ok();
 
     // Generate the client, with a valid auth token.
-    let client = ZohoClient::new(
+    let client = ZohoClient::builder(
         &env::var("ZOHO_CLIENT_ID")?,
         &env::var("ZOHO_CLIENT_SECRET")?,
     )
-    .set_portal(&env::var("ZOHO_PORTAL_NAME")?)?
-    .set_project(&env::var("ZOHO_PROJECT_NAME")?)?;
+    .portal(&env::var("ZOHO_PORTAL_NAME")?)
+    .project(&env::var("ZOHO_PROJECT_NAME")?)
+    .build()?;
 
-    let pjts = client.projects().get()?;
+    let pjts = client.portal_scope()?.projects().get()?;
     println!("Existing projects: {:?}", pjts);
 
     Ok(0)
@@ -7,20 +7,21 @@ fn run() -> Result<i32> {
     dotenv().ok();
 
     // Generate the client, with a valid auth token.
-    let client = ZohoClient::new(
+    let client = ZohoClient::builder(
         &env::var("ZOHO_CLIENT_ID")?,
         &env::var("ZOHO_CLIENT_SECRET")?,
     )
-    .set_portal(&env::var("ZOHO_PORTAL_NAME")?)?
-    .set_project(&env::var("ZOHO_PROJECT_NAME")?)?;
+    .portal(&env::var("ZOHO_PORTAL_NAME")?)
+    .project(&env::var("ZOHO_PROJECT_NAME")?)
+    .build()?;
 
     let tasks = client
+        .project_scope()?
         .tasks()
         .with_subtasks()
         .iter_get()
-        .filter(std::result::Result::is_ok)
-        .map(std::result::Result::unwrap)
-        .count();
+        .try_collect_all()?
+        .len();
     println!("Existing tasks and subtasks: {:#?}", tasks);
 
     Ok(0)
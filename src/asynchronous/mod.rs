@@ -0,0 +1,126 @@
+//! An async client for the Zoho Projects API, enabled with the `async` feature. It shares its
+//! credentials, rate limit and retry policy with a blocking ZohoClient, and the models and
+//! filters are the same; only sending requests differs, returning futures and streams rather
+//! than blocking. A Tokio runtime is required.
+//!
+//! ```no_run
+//! # async fn run() -> zohohorrorshow::errors::Result<()> {
+//! use futures::TryStreamExt;
+//! use zohohorrorshow::asynchronous;
+//! use zohohorrorshow::prelude::*;
+//!
+//! // Authorization and portal lookup happen once, up front, on the blocking client.
+//! let blocking = tokio::task::spawn_blocking(|| {
+//!     ZohoClient::builder("client-id", "client-secret")
+//!         .portal("portal")
+//!         .project("project")
+//!         .build()
+//! })
+//! .await
+//! .expect("client construction panicked")?;
+//! let client = asynchronous::ZohoClient::new(blocking);
+//!
+//! let bugs: Vec<_> = client.project_scope()?.bugs().stream().try_collect().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client;
+use crate::errors::*;
+use crate::models::portal;
+use crate::request::RequestParameters;
+use crate::scope::Ident;
+use std::sync::Arc;
+
+mod request;
+mod scope;
+mod transport;
+
+pub use self::request::{Paginator, Request};
+pub use self::scope::{PortalScope, ProjectScope};
+pub use self::transport::{ReqwestTransport, Transport};
+
+/// The async counterpart to ZohoClient. Clones share the same credentials and rate limit,
+/// with each other and with the blocking client this was made from.
+#[derive(Debug, Clone)]
+pub struct ZohoClient {
+    inner: client::ZohoClient,
+    transport: Arc<dyn Transport>,
+}
+
+impl ZohoClient {
+    /// Constructor for an async ZohoClient sending requests through a default async Reqwest
+    /// client, on behalf of the given blocking one
+    pub fn new(client: client::ZohoClient) -> Self {
+        ZohoClient::with_transport(client, ReqwestTransport::new())
+    }
+
+    /// Constructor for an async ZohoClient sending requests through the given Transport
+    pub fn with_transport(client: client::ZohoClient, transport: impl Transport + 'static) -> Self {
+        ZohoClient {
+            inner: client,
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// Accessor method for the blocking client whose credentials and settings this shares
+    pub fn blocking(&self) -> &client::ZohoClient {
+        &self.inner
+    }
+
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
+    }
+
+    /// Returns the access token for this Client, renewing it first if it has expired.
+    /// Renewal goes through the blocking OAuth flow, so runs on Tokio's blocking thread pool.
+    pub async fn access_token(&self) -> Result<String> {
+        if let Some(token) = self.inner.current_access_token() {
+            return Ok(token);
+        }
+
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.access_token())
+            .await
+            .map_err(|e| Error::transport(&e.to_string()))?
+    }
+
+    /// Wrap any model request, e.g. one adjusted with model-specific methods, to be sent
+    /// through this client
+    pub fn request<R: RequestParameters + Clone>(&self, request: R) -> Request<R> {
+        Request::new(self, request)
+    }
+
+    /// Scope requests to a Portal, given its ID or name. Looking up a name goes through the
+    /// blocking client, so runs on Tokio's blocking thread pool.
+    pub async fn portal(&self, portal: impl Into<Ident>) -> Result<PortalScope> {
+        let portal = portal.into();
+        let inner = self.inner.clone();
+        let scope = tokio::task::spawn_blocking(move || inner.portal(portal))
+            .await
+            .map_err(|e| Error::transport(&e.to_string()))??;
+        Ok(PortalScope::new(self, scope))
+    }
+
+    /// The Portal set on the blocking client, failing if there is none
+    pub fn portal_scope(&self) -> Result<PortalScope> {
+        Ok(PortalScope::new(self, self.inner.portal_scope()?))
+    }
+
+    /// The Project set on the blocking client, failing if there is none
+    pub fn project_scope(&self) -> Result<ProjectScope> {
+        Ok(ProjectScope::new(self, self.inner.project_scope()?))
+    }
+
+    /// Construct a Request for retrieving the available Portals for this Client.
+    /// Used when initializing a new Client.
+    pub fn portals(&self) -> Request<portal::PortalRequest> {
+        self.request(self.inner.portals())
+    }
+}
+
+impl From<client::ZohoClient> for ZohoClient {
+    fn from(client: client::ZohoClient) -> Self {
+        ZohoClient::new(client)
+    }
+}
@@ -0,0 +1,370 @@
+//! Async counterparts to the RequestParameters methods. A Request wraps one of the ordinary
+//! model requests, so the same filters and payloads apply, and sends it without blocking.
+
+use super::ZohoClient;
+use crate::errors::*;
+use crate::paginator::{self, Cursor};
+use crate::request::{
+    first, targets_collection, targets_record, Attempt, Deleted, FilterOptions, ModelOf,
+    RequestParameters, ZohoRequest,
+};
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::Method;
+
+/// A request to the Zoho API for one of the models, sent asynchronously. Created by the
+/// model methods of the async scopes, e.g. `project.bugs()`.
+#[derive(Clone, Debug)]
+pub struct Request<R> {
+    client: ZohoClient,
+    request: R,
+}
+
+impl<R> Request<R>
+where
+    R: RequestParameters + Clone,
+{
+    /// Constructor for a Request sending the given model request through the client
+    pub fn new(client: &ZohoClient, request: R) -> Self {
+        Request {
+            client: client.clone(),
+            request,
+        }
+    }
+
+    /// Accessor method for the underlying model request
+    pub fn request(&self) -> &R {
+        &self.request
+    }
+
+    /// Apply a change to the underlying model request, e.g. `TaskRequest::with_subtasks`
+    pub fn map(mut self, change: impl FnOnce(R) -> R) -> Self {
+        self.request = change(self.request);
+        self
+    }
+
+    /// Set filters to reduce the number of hits returned, as for the blocking request
+    pub fn filter(self, param: impl FilterOptions + std::fmt::Display) -> Self {
+        self.map(|request| request.filter(param))
+    }
+
+    /// Read the model. Where nothing matches, the collection is empty.
+    pub async fn get(&self) -> Result<R::ModelCollection> {
+        self.request.permits(&Method::GET)?;
+        ZohoRequest::read(&self.request.uri(), self.request.params())
+            .send_async(&self.client)
+            .await
+    }
+
+    /// Read the single item of the model with this request's ID, failing with
+    /// `Error::NotFound` if there is no such item. Fails with `Error::MissingRecordId`, without
+    /// sending anything, if the request has no ID.
+    pub async fn get_one(&self) -> Result<ModelOf<R>> {
+        targets_record(&self.request, &Method::GET)?;
+        first(self.get().await?, &self.request.uri())
+    }
+
+    /// Create a new item of the model, returning it as Zoho recorded it. Fails with
+    /// `Error::UnexpectedRecordId`, without sending anything, if the request has an ID.
+    pub async fn post(&self, data: R::NewModel) -> Result<ModelOf<R>> {
+        self.request.permits(&Method::POST)?;
+        targets_collection(&self.request, &Method::POST)?;
+        let files = self.request.uploads(&data);
+        let created: R::ModelCollection =
+            ZohoRequest::write(&self.request.uri(), data, self.request.params())
+                .with_files("uploadfile", &files)
+                .send_async(&self.client)
+                .await?;
+        first(created, &self.request.uri())
+    }
+
+    /// Update the item of the model with this request's ID, changing only the fields set in
+    /// `data`, and return it as Zoho now has it. Fails with `Error::MissingRecordId`, without
+    /// sending anything, if the request has no ID.
+    pub async fn put(&self, data: R::UpdateModel) -> Result<ModelOf<R>> {
+        self.request.permits(&Method::PUT)?;
+        targets_record(&self.request, &Method::PUT)?;
+        let updated: R::ModelCollection =
+            ZohoRequest::write(&self.request.uri(), data, self.request.params())
+                .send_async(&self.client)
+                .await?;
+        first(updated, &self.request.uri())
+    }
+
+    /// Delete the item of the model with this request's ID. Fails with
+    /// `Error::MissingRecordId`, without sending anything, if the request has no ID.
+    pub async fn delete(&self) -> Result<Deleted> {
+        self.request.permits(&Method::DELETE)?;
+        targets_record(&self.request, &Method::DELETE)?;
+        ZohoRequest::delete(&self.request.uri(), self.request.params())
+            .send_async(&self.client)
+            .await
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn, fetching them
+    /// from Zoho a page at a time
+    pub fn paginator(self) -> Paginator<R> {
+        Paginator::new(self)
+    }
+
+    /// Stream every record matching this request, however many pages they span, fetching them
+    /// from Zoho a page at a time. Nested records, such as subtasks where requested, follow the
+    /// rest. If a page cannot be fetched, the error is yielded and the stream ends there.
+    pub fn stream(self) -> impl Stream<Item = Result<ModelOf<R>>> {
+        self.paginator().into_stream()
+    }
+}
+
+/// The async counterpart to Paginator, iterating across every record matching a request and
+/// any records nested beneath them, such as subtasks where requested, which follow the rest.
+/// Created with `Request::paginator`.
+///
+/// If a page cannot be fetched, the error is returned in place of its records and iteration
+/// ends there.
+pub struct Paginator<R>
+where
+    R: RequestParameters + Clone,
+{
+    client: ZohoClient,
+    pages: paginator::Paginator<R>,
+}
+
+impl<R> Paginator<R>
+where
+    R: RequestParameters + Clone,
+{
+    /// Constructor for a Paginator over the given request
+    pub fn new(request: Request<R>) -> Self {
+        Paginator {
+            client: request.client,
+            pages: paginator::Paginator::new(request.request),
+        }
+    }
+
+    /// Fetch this many records per request, up to Zoho's limit of 100
+    pub fn page_size(mut self, page_size: u8) -> Self {
+        self.pages = self.pages.page_size(page_size);
+        self
+    }
+
+    /// Stop after this many records in total, counting nested records, requesting no more
+    /// than needed to reach it
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.pages = self.pages.max_items(max_items);
+        self
+    }
+
+    /// Continue from a Cursor saved earlier, rather than the first record
+    pub fn resume(mut self, cursor: Cursor) -> Self {
+        self.pages = self.pages.resume(cursor);
+        self
+    }
+
+    /// The position of the next record to be returned, including any nested listings still
+    /// to be fetched, to `resume` from later
+    pub fn cursor(&self) -> Cursor {
+        self.pages.cursor()
+    }
+
+    /// Fetch every remaining record, failing if any page cannot be fetched
+    pub async fn try_collect_all(self) -> Result<Vec<ModelOf<R>>> {
+        self.into_stream().try_collect().await
+    }
+
+    /// Return the next record, fetching the next page first if the current one is used up.
+    /// Returns Ok(None) once every record has been returned.
+    pub async fn try_next(&mut self) -> Result<Option<ModelOf<R>>> {
+        loop {
+            if let Some(item) = self.pages.take_fetched() {
+                return Ok(Some(item));
+            }
+
+            if let Some(request) = self.pages.next_page() {
+                let page = Request::new(&self.client, request).get().await;
+                self.pages.receive(page)?;
+            } else if !self.pages.next_listing() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Turn this Paginator into a Stream of the remaining records
+    pub fn into_stream(self) -> impl Stream<Item = Result<ModelOf<R>>> {
+        stream::try_unfold(self, Paginator::step)
+    }
+
+    async fn step(mut self) -> Result<Option<(ModelOf<R>, Self)>> {
+        Ok(self.try_next().await?.map(|item| (item, self)))
+    }
+}
+
+impl<R> std::fmt::Debug for Paginator<R>
+where
+    R: RequestParameters + Clone + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Paginator")
+            .field("pages", &self.pages)
+            .finish()
+    }
+}
+
+impl<T: serde::Serialize + Clone> ZohoRequest<T> {
+    /// The async counterpart to `send`, making the request through the async client's
+    /// Transport and waiting out rate limits and retries without blocking the thread.
+    pub(crate) async fn send_async<U>(&self, client: &ZohoClient) -> Result<U>
+    where
+        U: serde::de::DeserializeOwned + Default,
+    {
+        let policy = client.blocking().retry_policy();
+        let mut attempts = 0;
+        let response = loop {
+            // Rebuilt for each attempt, as a long wait may outlast the access token.
+            let request = self.http_request(&client.access_token().await?)?;
+            client.blocking().rate_limiter().acquire_async().await;
+            attempts += 1;
+
+            let outcome = client.transport().send(&request).await;
+            match self.assess(&policy, attempts, outcome)? {
+                Attempt::Done(response) => break response,
+                Attempt::Retry(wait) => tokio::time::sleep(wait).await,
+            }
+        };
+
+        self.parse(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client;
+    use crate::models::category::CategoryRequest;
+    use crate::models::task::{self, TaskRequest};
+    use crate::testing::task_json;
+    use crate::transport::MockTransport;
+    use reqwest::StatusCode;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn client(transport: &MockTransport) -> ZohoClient {
+        let blocking = client::ZohoClient::with_access_token("token", transport.clone());
+        ZohoClient::with_transport(blocking, transport.clone())
+    }
+
+    fn categories(transport: &MockTransport) -> Request<CategoryRequest> {
+        let client = client(transport);
+        let request =
+            CategoryRequest::new(client.blocking(), "portal/1/projects/2/categories/", None);
+        client.request(request)
+    }
+
+    fn page(transport: &MockTransport, ids: &[i64]) {
+        let categories: Vec<String> = ids
+            .iter()
+            .map(|id| format!(r#"{{"id": "{}", "name": "Category {}"}}"#, id, id))
+            .collect();
+        transport.respond(
+            StatusCode::OK,
+            &format!(r#"{{"categories": [{}]}}"#, categories.join(",")),
+        );
+    }
+
+    fn indices(transport: &MockTransport) -> Vec<String> {
+        transport
+            .requests()
+            .iter()
+            .map(|request| request.query_param("index").unwrap_or_default().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn stream_yields_every_page() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        page(&transport, &[3]);
+
+        let stream = categories(&transport)
+            .paginator()
+            .page_size(2)
+            .into_stream();
+        let found: Vec<i64> = block_on(stream.map_ok(|category| category.id).try_collect())
+            .expect("every page fetched");
+        assert_eq!(found, vec![1, 2, 3]);
+        assert_eq!(indices(&transport), vec!["0", "2"]);
+    }
+
+    #[test]
+    fn stream_ends_with_the_failed_page() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        transport.respond(StatusCode::BAD_REQUEST, "");
+
+        let stream = categories(&transport)
+            .paginator()
+            .page_size(2)
+            .into_stream();
+        let found: Vec<Result<_>> = block_on(futures::StreamExt::collect(stream));
+        assert_eq!(found.len(), 3);
+        assert!(found[..2].iter().all(Result::is_ok));
+        assert!(found[2].is_err());
+    }
+
+    #[test]
+    fn resume_continues_from_the_cursor() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        let mut paginator = categories(&transport).paginator().page_size(2);
+        block_on(paginator.try_next()).unwrap();
+        let cursor = paginator.cursor();
+
+        let resumed_transport = MockTransport::new();
+        page(&resumed_transport, &[2]);
+        let resumed = categories(&resumed_transport)
+            .paginator()
+            .page_size(2)
+            .resume(cursor);
+        let found = block_on(resumed.try_collect_all()).unwrap();
+        assert_eq!(found.iter().map(|c| c.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(indices(&resumed_transport), vec!["1"]);
+    }
+
+    #[test]
+    fn subtasks_follow_their_parents() {
+        let transport = MockTransport::new();
+        let tasks = serde_json::json!({ "tasks": [task_json(1, true), task_json(2, false)] });
+        transport.respond(StatusCode::OK, &tasks.to_string());
+        let subtasks = serde_json::json!({ "tasks": [task_json(11, false)] });
+        transport.respond(StatusCode::OK, &subtasks.to_string());
+
+        let client = client(&transport);
+        let request = TaskRequest::new(client.blocking(), &task::model_path(1, 2), None);
+        let found = block_on(
+            client
+                .request(request.with_subtasks())
+                .paginator()
+                .try_collect_all(),
+        )
+        .unwrap();
+        assert_eq!(
+            found.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![1, 2, 11]
+        );
+        assert!(transport.requests()[1]
+            .url
+            .ends_with("/projects/2/tasks/1/subtasks/"));
+    }
+
+    #[test]
+    fn get_one_without_an_id_is_refused() {
+        let transport = MockTransport::new();
+        let result = block_on(categories(&transport).get_one());
+        assert!(matches!(result, Err(Error::MissingRecordId { .. })));
+        assert!(transport.requests().is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+//! Async counterparts to PortalScope and ProjectScope, whose requests are sent through an
+//! async ZohoClient.
+
+use super::{Request, ZohoClient};
+use crate::errors::*;
+use crate::models::{
+    activity, bug, category, event, forum, milestone, portal, project, status, task, tasklist,
+    timesheet,
+};
+use crate::scope::{self, Ident};
+
+/// Requests within a single Portal, sent without blocking. Created with `ZohoClient::portal`.
+#[derive(Clone, Debug)]
+pub struct PortalScope {
+    client: ZohoClient,
+    scope: scope::PortalScope,
+}
+
+impl PortalScope {
+    pub(crate) fn new(client: &ZohoClient, scope: scope::PortalScope) -> Self {
+        PortalScope {
+            client: client.clone(),
+            scope,
+        }
+    }
+
+    /// Accessor method for the ID of this Portal
+    pub fn id(&self) -> i64 {
+        self.scope.id()
+    }
+
+    /// Accessor method for the blocking scope this wraps
+    pub fn blocking(&self) -> &scope::PortalScope {
+        &self.scope
+    }
+
+    /// Scope requests to a Project within this Portal, given its ID or name. Looking up a
+    /// name goes through the blocking client, so runs on Tokio's blocking thread pool.
+    pub async fn project(&self, project: impl Into<Ident>) -> Result<ProjectScope> {
+        let project = project.into();
+        let portal = self.scope.clone();
+        let scope = tokio::task::spawn_blocking(move || portal.project(project))
+            .await
+            .map_err(|e| Error::transport(&e.to_string()))??;
+        Ok(ProjectScope::new(&self.client, scope))
+    }
+
+    /// Construct a Request for retrieving the users with access to this Portal
+    pub fn portal_users(&self) -> Request<portal::user::PortalUserRequest> {
+        self.client.request(self.scope.portal_users())
+    }
+
+    /// Construct a Request for retrieving the authorized user's Tasks across every Project
+    /// in this Portal
+    pub fn my_tasks(&self) -> Request<task::MyTaskRequest> {
+        self.client.request(self.scope.my_tasks())
+    }
+
+    /// Construct a Request for retrieving the authorized user's Bugs across every Project
+    /// in this Portal
+    pub fn my_bugs(&self) -> Request<bug::MyBugRequest> {
+        self.client.request(self.scope.my_bugs())
+    }
+
+    /// Construct a Request for retrieving multiple Projects
+    pub fn projects(&self) -> Request<project::ProjectRequest> {
+        self.client.request(self.scope.projects())
+    }
+}
+
+/// Requests within a single Project, sent without blocking. Created with
+/// `PortalScope::project`, or `ZohoClient::project_scope`.
+#[derive(Clone, Debug)]
+pub struct ProjectScope {
+    client: ZohoClient,
+    scope: scope::ProjectScope,
+}
+
+impl ProjectScope {
+    pub(crate) fn new(client: &ZohoClient, scope: scope::ProjectScope) -> Self {
+        ProjectScope {
+            client: client.clone(),
+            scope,
+        }
+    }
+
+    /// Accessor method for the ID of this Project
+    pub fn id(&self) -> i64 {
+        self.scope.id()
+    }
+
+    /// The Portal this Project belongs to
+    pub fn portal(&self) -> PortalScope {
+        PortalScope::new(&self.client, self.scope.portal())
+    }
+
+    /// Accessor method for the blocking scope this wraps
+    pub fn blocking(&self) -> &scope::ProjectScope {
+        &self.scope
+    }
+
+    /// Construct a Request for retrieving this Project
+    pub fn project(&self) -> Request<project::ProjectRequest> {
+        self.client.request(self.scope.project())
+    }
+
+    /// Construct a Request for retrieving multiple Activities. Activities cannot be
+    /// requested singly, therefore there is no id-parameterised counterpart.
+    pub fn activities(&self) -> Request<activity::ActivityRequest> {
+        self.client.request(self.scope.activities())
+    }
+
+    /// Construct a Request for retrieving a Bug by numeric ID
+    pub fn bug(&self, id: i64) -> Request<bug::BugRequest> {
+        self.client.request(self.scope.bug(id))
+    }
+
+    /// Construct a Request for retrieving multiple Bugs
+    pub fn bugs(&self) -> Request<bug::BugRequest> {
+        self.client.request(self.scope.bugs())
+    }
+
+    /// Construct a Request for retrieving a Category by ID
+    pub fn category(&self, id: i64) -> Request<category::CategoryRequest> {
+        self.client.request(self.scope.category(id))
+    }
+
+    /// Construct a Request for retrieving multiple Categories
+    pub fn categories(&self) -> Request<category::CategoryRequest> {
+        self.client.request(self.scope.categories())
+    }
+
+    /// Construct a Request for retrieving an Event by ID
+    pub fn event(&self, id: i64) -> Request<event::EventRequest> {
+        self.client.request(self.scope.event(id))
+    }
+
+    /// Construct a Request for retrieving multiple Events
+    pub fn events(&self) -> Request<event::EventRequest> {
+        self.client.request(self.scope.events())
+    }
+
+    /// Construct a Request for retrieving a Forum by ID
+    pub fn forum(&self, id: i64) -> Request<forum::ForumRequest> {
+        self.client.request(self.scope.forum(id))
+    }
+
+    /// Construct a Request for retrieving multiple Fora
+    pub fn forums(&self) -> Request<forum::ForumRequest> {
+        self.client.request(self.scope.forums())
+    }
+
+    /// Construct a Request for retrieving a Comment within a Forum, referencing the
+    /// ID of both the Forum and Comment
+    pub fn forum_comment(&self, forum_id: i64, id: i64) -> Request<forum::comment::CommentRequest> {
+        self.client.request(self.scope.forum_comment(forum_id, id))
+    }
+
+    /// Construct a Request for retrieving multiple Comments within a Forum, referencing
+    /// the Forum ID
+    pub fn forum_comments(&self, forum_id: i64) -> Request<forum::comment::CommentRequest> {
+        self.client.request(self.scope.forum_comments(forum_id))
+    }
+
+    /// Construct a Request for retrieving a Milestone by ID
+    pub fn milestone(&self, id: i64) -> Request<milestone::MilestoneRequest> {
+        self.client.request(self.scope.milestone(id))
+    }
+
+    /// Construct a Request for retrieving multiple Milestones
+    pub fn milestones(&self) -> Request<milestone::MilestoneRequest> {
+        self.client.request(self.scope.milestones())
+    }
+
+    /// Construct a Request for retrieving the Users with access to this Project
+    pub fn project_users(&self) -> Request<project::user::ProjectUserRequest> {
+        self.client.request(self.scope.project_users())
+    }
+
+    /// Construct a Request for retrieving multiple Statuses. There is no singular counterpart.
+    pub fn statuses(&self) -> Request<status::StatusRequest> {
+        self.client.request(self.scope.statuses())
+    }
+
+    /// Construct a Request for retrieving a Task by ID
+    pub fn task(&self, id: i64) -> Request<task::TaskRequest> {
+        self.client.request(self.scope.task(id))
+    }
+
+    /// Construct a Request for retrieving multiple Tasks
+    pub fn tasks(&self) -> Request<task::TaskRequest> {
+        self.client.request(self.scope.tasks())
+    }
+
+    /// Construct a Request for retrieving a Tasklist by ID
+    pub fn tasklist(&self, id: i64) -> Request<tasklist::TasklistRequest> {
+        self.client.request(self.scope.tasklist(id))
+    }
+
+    /// Construct a Request for retrieving multiple Tasklists
+    pub fn tasklists(&self) -> Request<tasklist::TasklistRequest> {
+        self.client.request(self.scope.tasklists())
+    }
+
+    /// Construct a Request for retrieving a Task belonging to a specific Tasklist
+    pub fn tasklist_task(
+        &self,
+        tasklist_id: usize,
+        id: i64,
+    ) -> Request<tasklist::task::TasklistTaskRequest> {
+        self.client
+            .request(self.scope.tasklist_task(tasklist_id, id))
+    }
+
+    /// Construct a Request for retrieving Tasks belonging to a specific Tasklist
+    pub fn tasklist_tasks(
+        &self,
+        tasklist_id: usize,
+    ) -> Request<tasklist::task::TasklistTaskRequest> {
+        self.client.request(self.scope.tasklist_tasks(tasklist_id))
+    }
+
+    /// Construct a Request for retrieving a Timesheet by ID
+    pub fn timesheet(&self, id: i64) -> Request<timesheet::TimesheetRequest> {
+        self.client.request(self.scope.timesheet(id))
+    }
+
+    /// Construct a Request for retrieving multiple Timesheets
+    pub fn timesheets(&self) -> Request<timesheet::TimesheetRequest> {
+        self.client.request(self.scope.timesheets())
+    }
+}
@@ -0,0 +1,73 @@
+//! The async counterpart to the transport module: the HTTP layer underneath an async
+//! ZohoClient. Requests and responses are the same HttpRequest and HttpResponse types.
+
+use crate::errors::*;
+use crate::transport::{HttpRequest, HttpResponse, MockTransport};
+use futures::future::{self, BoxFuture};
+use reqwest::header::CONTENT_TYPE;
+
+/// A Transport is responsible for delivering an HttpRequest and returning the server's response.
+/// The async ZohoClient holds one and routes every request through it.
+pub trait Transport: std::fmt::Debug + Send + Sync {
+    /// Deliver the request and return whatever the server responded with. Non-success status
+    /// codes are not errors at this level; only a failure to get a response at all is.
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse>>;
+}
+
+/// The default async Transport, which makes real requests using an async Reqwest client.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Constructor for a ReqwestTransport with a default Reqwest client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructor for a ReqwestTransport wrapping a preconfigured Reqwest client,
+    /// e.g. one with a proxy or custom timeouts.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method.clone(), &request.url)
+                .headers(request.headers.clone());
+            if !request.query.is_empty() {
+                builder = builder.query(&request.query);
+            }
+            if let Some(ref body) = request.body {
+                builder = builder
+                    .header(CONTENT_TYPE, body.content_type())
+                    .body(body.to_bytes());
+            }
+
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+// Canned responses are ready immediately, so the blocking implementation serves for both.
+impl Transport for MockTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> BoxFuture<'a, Result<HttpResponse>> {
+        Box::pin(future::ready(crate::transport::Transport::send(
+            self, request,
+        )))
+    }
+}
@@ -1,17 +1,85 @@
 //! The client module contains the implementations for the API client itself and wraps the OAuth process necessary
 //! to make requests for a user's data.
 //! If in doubt, this is the one to import.
-//! Each model is provided its own plural-named method on ZohoClient for retrieving many entries.
-//! Where possible there is also a singular-names method which takes an ID parameter to retrieve a single entry.
-//! Some entities such as Activities are only available from the Zoho API as a list; in these cases a singular call has not been provided.
+//! Requests for the models within a Portal or Project are made through a PortalScope or
+//! ProjectScope obtained from the client; see the scope module.
 
 use crate::errors::*;
-use crate::models::{
-    activity, bug, category, event, forum, milestone, portal, project, status, task, tasklist,
-    timesheet,
-};
+use crate::models::portal;
 use crate::oauth;
-use crate::request::RequestParameters;
+pub use crate::oauth::{AuthMode, RevokedTokens, Scopes};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::scope::{find_by_name, Ident, PortalScope, ProjectScope};
+use crate::token_store::TokenStore;
+use crate::transport::{ReqwestTransport, Transport};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::warn;
+
+/// The Zoho data centres in which an account may be hosted. Each has its own accounts server
+/// for OAuth and its own Projects API host; a client must use the pair matching the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataCentre {
+    /// United States, zoho.com
+    #[default]
+    Com,
+    /// Europe, zoho.eu
+    Eu,
+    /// India, zoho.in
+    In,
+    /// Australia, zoho.com.au
+    ComAu,
+    /// Japan, zoho.jp
+    Jp,
+    /// China, zoho.com.cn
+    ComCn,
+}
+
+impl DataCentre {
+    /// The top level domain suffix used by this data centre, e.g. `eu` for zoho.eu
+    pub fn suffix(self) -> &'static str {
+        match self {
+            DataCentre::Com => "com",
+            DataCentre::Eu => "eu",
+            DataCentre::In => "in",
+            DataCentre::ComAu => "com.au",
+            DataCentre::Jp => "jp",
+            DataCentre::ComCn => "com.cn",
+        }
+    }
+
+    /// Base URL of the Zoho Projects REST API in this data centre
+    pub fn api_base_url(self) -> String {
+        format!("https://projectsapi.zoho.{}/restapi", self.suffix())
+    }
+
+    /// Base URL of the Zoho accounts server, which issues OAuth tokens, in this data centre
+    pub fn accounts_url(self) -> String {
+        format!("https://accounts.zoho.{}", self.suffix())
+    }
+
+    /// Identify the data centre from an `api_domain` as returned alongside an OAuth token,
+    /// e.g. `https://www.zohoapis.eu`.
+    pub fn from_api_domain(api_domain: &str) -> Option<Self> {
+        let host = api_domain
+            .trim_end_matches('/')
+            .rsplit("://")
+            .next()
+            .unwrap_or(api_domain);
+        // Check the two-part suffixes first, so that zohoapis.com.au isn't taken for zohoapis.com
+        [
+            DataCentre::ComAu,
+            DataCentre::ComCn,
+            DataCentre::Com,
+            DataCentre::Eu,
+            DataCentre::In,
+            DataCentre::Jp,
+        ]
+        .iter()
+        .copied()
+        .find(|dc| host.ends_with(&format!(".{}", dc.suffix())))
+    }
+}
 
 /// ZohoClient initialises and manages the details with which we can make
 /// a connection to the Zoho API. It holds the information required which
@@ -19,339 +87,601 @@ use crate::request::RequestParameters;
 /// specific endpoint.
 #[derive(Debug, Clone)]
 pub struct ZohoClient {
-    oauth_credentials: oauth::Credentials,
+    // Shared between clones, so that a token refreshed by one is used by all.
+    oauth_credentials: Arc<Mutex<oauth::Credentials>>,
+    transport: Arc<dyn Transport>,
+    // Likewise shared, as Zoho's quota applies to the user rather than to each connection.
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    data_centre: DataCentre,
+    api_base_url: Option<String>,
     portal_id: Option<i64>,
     project_id: Option<i64>,
 }
 
-impl ZohoClient {
-    /// Generate new ZohoClient which may be used to make requests
+/// Builder for a ZohoClient, created with [`ZohoClient::builder`]. Every option has a default,
+/// so only the client ID and secret from the Zoho API console are required. Nothing contacts
+/// Zoho until [`build`] is called.
+///
+/// ```no_run
+/// # use zohohorrorshow::prelude::*;
+/// # fn main() -> zohohorrorshow::errors::Result<()> {
+/// let client = ZohoClient::builder("client_id", "client_secret")
+///     .scopes(Scopes::read_only())
+///     .redirect_uri("http://localhost:9090/")
+///     .portal("My Portal")
+///     .project("My Project")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ZohoClient::builder`]: struct.ZohoClient.html#method.builder
+/// [`build`]: #method.build
+#[derive(Debug, Clone)]
+pub struct ZohoClientBuilder {
+    client_id: String,
+    client_secret: Option<String>,
+    pkce: bool,
+    data_centre: DataCentre,
+    accounts_url: Option<String>,
+    api_base_url: Option<String>,
+    transport: Option<Arc<dyn Transport>>,
+    rate_limit: RateLimit,
+    retry_policy: RetryPolicy,
+    token_store: Option<Arc<dyn TokenStore>>,
+    auth_mode: AuthMode,
+    scopes: Scopes,
+    redirect_uri: Option<String>,
+    listen_addr: Option<String>,
+    portal: Option<Ident>,
+    project: Option<Ident>,
+}
+
+impl ZohoClientBuilder {
+    /// Constructor for a new ZohoClientBuilder, using the client ID and secret from the Zoho API console
     pub fn new(client_id: &str, client_secret: &str) -> Self {
-        let credentials = oauth::Credentials::new(client_id, client_secret, None, None);
+        ZohoClientBuilder {
+            client_secret: Some(client_secret.to_owned()),
+            ..ZohoClientBuilder::public(client_id)
+        }
+    }
+
+    /// Constructor for a new ZohoClientBuilder for a public client, which has no secret.
+    /// Such clients always authorize using PKCE, so are suitable for desktop tools which
+    /// cannot keep a secret safe.
+    pub fn public(client_id: &str) -> Self {
+        ZohoClientBuilder {
+            client_id: client_id.to_owned(),
+            client_secret: None,
+            pkce: true,
+            data_centre: DataCentre::default(),
+            accounts_url: None,
+            api_base_url: None,
+            transport: None,
+            rate_limit: RateLimit::default(),
+            retry_policy: RetryPolicy::default(),
+            token_store: None,
+            auth_mode: AuthMode::default(),
+            scopes: Scopes::default(),
+            redirect_uri: None,
+            listen_addr: None,
+            portal: None,
+            project: None,
+        }
+    }
+
+    /// Authorise against, and make requests to, the given data centre
+    pub fn data_centre(mut self, data_centre: DataCentre) -> Self {
+        self.data_centre = data_centre;
+        self
+    }
+
+    /// Authorise against a custom accounts server in place of the data centre's,
+    /// e.g. `https://accounts.zoho.eu` or a local stand-in.
+    pub fn accounts_url(mut self, accounts_url: &str) -> Self {
+        self.accounts_url = Some(accounts_url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Make requests to a fully custom base URL; see [`ZohoClient::set_api_base_url`]
+    ///
+    /// [`ZohoClient::set_api_base_url`]: struct.ZohoClient.html#method.set_api_base_url
+    pub fn api_base_url(mut self, api_base_url: &str) -> Self {
+        self.api_base_url = Some(api_base_url.to_owned());
+        self
+    }
+
+    /// Send requests through the given Transport rather than the default Reqwest-backed one
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Load tokens from, and save them to, the given TokenStore. If the store holds a usable
+    /// access or refresh token no authorization flow is needed at all.
+    pub fn token_store(mut self, token_store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(token_store));
+        self
+    }
+
+    /// Choose how the first access token is obtained; the browser flow is used by default.
+    pub fn auth_mode(mut self, auth_mode: AuthMode) -> Self {
+        self.auth_mode = auth_mode;
+        self
+    }
+
+    /// Authorise with a grant code generated for a Self Client in the Zoho API console,
+    /// without a browser or a local listener.
+    pub fn self_client(self, grant_code: &str) -> Self {
+        self.auth_mode(AuthMode::SelfClient(grant_code.to_owned()))
+    }
+
+    /// Authorise by printing the authorization URL and reading the redirected URL,
+    /// or the code from it, from stdin.
+    pub fn manual(self) -> Self {
+        self.auth_mode(AuthMode::Manual)
+    }
+
+    /// Hold requests to the given quota; [`RateLimit::zoho`] by default.
+    ///
+    /// [`RateLimit::zoho`]: ../rate_limit/struct.RateLimit.html#method.zoho
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Retry failed requests according to the given policy; [`RetryPolicy::new`] by default.
+    ///
+    /// [`RetryPolicy::new`]: ../retry/struct.RetryPolicy.html#method.new
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Use PKCE when authorizing, binding the authorization code to this client so that it
+    /// is useless if intercepted. Always on for public clients.
+    pub fn pkce(mut self) -> Self {
+        self.pkce = true;
+        self
+    }
+
+    /// Request the given scopes when authorizing; [`Scopes::full`] by default.
+    ///
+    /// [`Scopes::full`]: struct.Scopes.html#method.full
+    pub fn scopes(mut self, scopes: Scopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Have Zoho redirect to this URI after authorization; `http://localhost:8080/` by
+    /// default. It must match one registered for the client in the Zoho API console.
+    pub fn redirect_uri(mut self, redirect_uri: &str) -> Self {
+        self.redirect_uri = Some(redirect_uri.to_owned());
+        self
+    }
+
+    /// Listen for the authorization redirect on this address, e.g. `127.0.0.1:9090`.
+    /// By default this is 127.0.0.1 on the port of the redirect URI.
+    pub fn listen_addr(mut self, listen_addr: &str) -> Self {
+        self.listen_addr = Some(listen_addr.to_owned());
+        self
+    }
+
+    /// Select the Portal with this name once authorized; see [`ZohoClient::set_portal`]
+    ///
+    /// [`ZohoClient::set_portal`]: struct.ZohoClient.html#method.set_portal
+    pub fn portal(mut self, portal_name: &str) -> Self {
+        self.portal = Some(Ident::from(portal_name));
+        self
+    }
+
+    /// Select the Portal with this ID once authorized, without looking it up
+    pub fn portal_id(mut self, portal_id: i64) -> Self {
+        self.portal = Some(Ident::Id(portal_id));
+        self
+    }
+
+    /// Select the Project with this name, within the selected Portal, once authorized;
+    /// see [`ZohoClient::set_project`]
+    ///
+    /// [`ZohoClient::set_project`]: struct.ZohoClient.html#method.set_project
+    pub fn project(mut self, project_name: &str) -> Self {
+        self.project = Some(Ident::from(project_name));
+        self
+    }
+
+    /// Select the Project with this ID, within the selected Portal, once authorized,
+    /// without looking it up
+    pub fn project_id(mut self, project_id: i64) -> Self {
+        self.project = Some(Ident::Id(project_id));
+        self
+    }
+
+    /// Generate the ZohoClient, obtaining an access token as configured and selecting the
+    /// Portal and Project if given. Fails if no token could be obtained, e.g. because
+    /// authorization was refused, or if the Portal or Project cannot be found.
+    pub fn build(self) -> Result<ZohoClient> {
+        let mut credentials =
+            oauth::Credentials::new(&self.client_id, self.client_secret.as_deref(), None, None);
+        credentials.set_pkce(self.pkce);
+        credentials.set_auth_mode(self.auth_mode);
+        credentials.set_scopes(self.scopes);
+        if let Some(ref redirect_uri) = self.redirect_uri {
+            credentials.set_redirect_uri(redirect_uri);
+        }
+        if let Some(ref listen_addr) = self.listen_addr {
+            credentials.set_listen_addr(listen_addr);
+        }
+        if let Some(store) = self.token_store {
+            if let Err(err) = credentials.set_store(store) {
+                warn!("Failed to load Zoho tokens from store: {}", err);
+            }
+        }
+
+        // Tokens loaded from a store record the data centre which issued them, and can only
+        // be renewed there.
+        let data_centre = credentials
+            .api_domain()
+            .and_then(|domain| DataCentre::from_api_domain(&domain))
+            .unwrap_or(self.data_centre);
+        credentials.set_accounts_url(
+            &self
+                .accounts_url
+                .unwrap_or_else(|| data_centre.accounts_url()),
+        );
 
         let mut client = ZohoClient {
-            oauth_credentials: credentials,
+            oauth_credentials: Arc::new(Mutex::new(credentials)),
+            transport: self
+                .transport
+                .unwrap_or_else(|| Arc::new(ReqwestTransport::new())),
+            rate_limiter: Arc::new(RateLimiter::new(self.rate_limit)),
+            retry_policy: self.retry_policy,
+            data_centre,
+            api_base_url: None,
             portal_id: None,
             project_id: None,
         };
 
-        client.initial_access_token();
-        client
+        // Fetch API access token from OAuth
+        client.access_token()?;
+
+        // Zoho reports where the account actually lives alongside the token; prefer that
+        // over whatever was assumed when the client was created.
+        let api_domain = client.credentials().api_domain();
+        if let Some(dc) = api_domain.and_then(|domain| DataCentre::from_api_domain(&domain)) {
+            client.data_centre = dc;
+        }
+
+        if let Some(ref url) = self.api_base_url {
+            client = client.set_api_base_url(url);
+        }
+        client = match self.portal {
+            Some(Ident::Id(portal_id)) => client.set_portal_id(portal_id),
+            Some(Ident::Name(ref portal_name)) => client.set_portal(portal_name)?,
+            None => client,
+        };
+        client = match self.project {
+            Some(Ident::Id(project_id)) => client.set_project_id(project_id),
+            Some(Ident::Name(ref project_name)) => client.set_project(project_name)?,
+            None => client,
+        };
+
+        Ok(client)
     }
+}
 
-    // Fetch API access token from OAuth
-    fn initial_access_token(&mut self) -> String {
-        self.oauth_credentials.access_token()
+impl ZohoClient {
+    /// Start building a new ZohoClient which may be used to make requests, using the client
+    /// ID and secret from the Zoho API console.
+    pub fn builder(client_id: &str, client_secret: &str) -> ZohoClientBuilder {
+        ZohoClientBuilder::new(client_id, client_secret)
     }
 
-    /// Returns the access token for this Client.
-    ///
-    /// # Panics
+    /// Start building a new ZohoClient for a public client, which has only a client ID and
+    /// authorizes using PKCE; see [`ZohoClientBuilder::public`]
     ///
-    /// Will panic if an access token has not been set for this client. As a ZohoClient
-    /// cannot be constructed other than via [`new`] and that calls the
-    /// necessary API endpoint to set the access token, this is not recoverable.
+    /// [`ZohoClientBuilder::public`]: struct.ZohoClientBuilder.html#method.public
+    pub fn public_builder(client_id: &str) -> ZohoClientBuilder {
+        ZohoClientBuilder::public(client_id)
+    }
+
+    /// Generate a ZohoClient around an access token which has already been obtained, skipping
+    /// the OAuth flow entirely. Paired with a [`MockTransport`] this allows code built on this
+    /// crate to be tested without network access.
     ///
-    /// [`new`]: #method.new
-    pub fn access_token(&self) -> String {
+    /// [`MockTransport`]: ../transport/struct.MockTransport.html
+    pub fn with_access_token(access_token: &str, transport: impl Transport + 'static) -> Self {
+        ZohoClient {
+            oauth_credentials: Arc::new(Mutex::new(oauth::Credentials::with_access_token(
+                access_token,
+            ))),
+            transport: Arc::new(transport),
+            rate_limiter: Arc::new(RateLimiter::default()),
+            retry_policy: RetryPolicy::default(),
+            data_centre: DataCentre::default(),
+            api_base_url: None,
+            portal_id: None,
+            project_id: None,
+        }
+    }
+
+    fn credentials(&self) -> MutexGuard<'_, oauth::Credentials> {
+        // Nothing in Credentials can panic part-way through an update, so a poisoned lock
+        // still holds usable credentials.
         self.oauth_credentials
-            .raw_access_token()
-            .expect("Client was incompletely initialized")
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
     }
 
-    /// Set the Portal to which this ZohoClient should make requests. This takes a Portal name;
-    /// these are typically human-friendly strings and should be known by the Zoho Portal users.
-    pub fn set_portal(mut self, portal_name: &str) -> Result<Self> {
-        let portals = self.portals().get();
-        let portal = match portals {
-            Ok(Some(p_list)) => p_list.portals.into_iter().find(|p| p.name == portal_name),
-            Ok(None) => return Err(Error::empty_entity_list("portal")),
-            Err(e) => return Err(e),
-        };
+    /// Returns the access token for this Client. If the token has expired it is first renewed,
+    /// using the refresh token where Zoho issued one; this is called before every request, so
+    /// a long-lived ZohoClient keeps working without further authorization.
+    pub fn access_token(&self) -> Result<String> {
+        self.credentials()
+            .access_token(self.transport(), &self.retry_policy)
+    }
 
-        if let Some(po) = portal {
-            self.portal_id = Some(po.id)
-        } else {
-            return Err(Error::missing_entity_name(portal_name));
-        };
+    /// Returns the access token for this Client if it is still valid, without renewing it.
+    #[cfg(feature = "async")]
+    pub(crate) fn current_access_token(&self) -> Option<String> {
+        self.credentials().current_access_token()
+    }
 
-        Ok(self)
+    /// Revoke this Client's tokens with Zoho and clear them from its token store, e.g. when
+    /// a credential may have leaked. Clones share the same tokens, so are revoked too; any
+    /// further request will need authorizing again.
+    pub fn revoke(&self) -> Result<RevokedTokens> {
+        self.credentials().revoke(self.transport())
     }
 
-    /// Set the Project to which this ZohoClient should refer when making requests.
-    /// All entities requested will be within this Project; if multiple Projects
-    /// are to be queried this can be called a second time to re-use the client.
-    /// The Project names are typically human-friendly strings and should be known
-    /// by the Zoho Project users.
-    pub fn set_project(mut self, project_name: &str) -> Result<Self> {
-        let projects = self.projects().get();
-        let project = match projects {
-            Ok(Some(p_list)) => p_list.projects.into_iter().find(|p| p.name == project_name),
-            Ok(None) => return Err(Error::empty_entity_list("project")),
-            Err(e) => return Err(e),
-        };
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
+    }
 
-        if let Some(pr) = project {
-            self.project_id = Some(pr.id)
-        } else {
-            return Err(Error::missing_entity_name(project_name));
-        };
+    pub(crate) fn rate_limiter(&self) -> &RateLimiter {
+        self.rate_limiter.as_ref()
+    }
 
-        Ok(self)
+    /// The policy under which this ZohoClient retries failed requests
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
     }
 
-    pub(crate) fn portal_id(&self) -> i64 {
-        self.portal_id
-            .expect("Portal context called without portal id set.")
+    /// Retry failed requests from this ZohoClient according to a different policy
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
-    pub(crate) fn project_id(&self) -> i64 {
-        self.project_id
-            .expect("Project context called without project id set.")
-    }
-
-    /// Construct a Request for retrieving multiple Activities. Activities cannot be
-    /// requested singly, therefore there is no id-parameterised counterpart.
-    pub fn activities(&self) -> activity::ActivityRequest {
-        activity::ActivityRequest::new(
-            &self.access_token(),
-            &activity::model_path(self.portal_id(), self.project_id()),
-        )
-    }
-
-    /// Construct a Request for retrieving a Bug by numeric ID
-    pub fn bug(&self, id: i64) -> bug::BugRequest {
-        bug::BugRequest::new(
-            &self.access_token(),
-            &bug::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Bugs
-    pub fn bugs(&self) -> bug::BugRequest {
-        bug::BugRequest::new(
-            &self.access_token(),
-            &bug::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+    /// Hold requests from this ZohoClient to a different quota. This gives it a limiter of
+    /// its own, no longer shared with the clones it was made from.
+    pub fn set_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiter = Arc::new(RateLimiter::new(rate_limit));
+        self
     }
 
-    /// Construct a Request for retrieving a Category by ID
-    pub fn category(&self, id: i64) -> category::CategoryRequest {
-        category::CategoryRequest::new(
-            &self.access_token(),
-            &category::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
+    /// Set the data centre whose Projects API this ZohoClient should make requests to.
+    /// This does not re-authorise; tokens are only valid in the data centre which issued them.
+    pub fn set_data_centre(mut self, data_centre: DataCentre) -> Self {
+        self.data_centre = data_centre;
+        self
     }
 
-    /// Construct a Request for retrieving multiple Categories
-    pub fn categories(&self) -> category::CategoryRequest {
-        category::CategoryRequest::new(
-            &self.access_token(),
-            &category::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+    /// Send all requests to a fully custom base URL in place of the data centre's Projects API,
+    /// e.g. a local stand-in server. Model paths are appended to this, so it should correspond
+    /// to `https://projectsapi.zoho.com/restapi`.
+    pub fn set_api_base_url(mut self, api_base_url: &str) -> Self {
+        self.api_base_url = Some(api_base_url.trim_end_matches('/').to_owned());
+        self
     }
 
-    /// Construct a Request for retrieving an Event by ID
-    pub fn event(&self, id: i64) -> event::EventRequest {
-        event::EventRequest::new(
-            &self.access_token(),
-            &event::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
+    /// The data centre this ZohoClient is making requests to
+    pub fn data_centre(&self) -> DataCentre {
+        self.data_centre
     }
 
-    /// Construct a Request for retrieving multiple Events
-    pub fn events(&self) -> event::EventRequest {
-        event::EventRequest::new(
-            &self.access_token(),
-            &event::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+    /// The base URL to which model paths are appended when making requests
+    pub fn api_base_url(&self) -> String {
+        self.api_base_url
+            .clone()
+            .unwrap_or_else(|| self.data_centre.api_base_url())
     }
 
-    /// Construct a Request for retrieving a Forum by ID
-    pub fn forum(&self, id: i64) -> forum::ForumRequest {
-        forum::ForumRequest::new(
-            &self.access_token(),
-            &forum::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
+    /// Set the Portal to which this ZohoClient should make requests. This takes a Portal name;
+    /// these are typically human-friendly strings and should be known by the Zoho Portal users.
+    /// A Portal whose name or `id_string` is exactly the one given is chosen first; failing
+    /// that, one whose name matches ignoring case. A name matching several is an error listing
+    /// them.
+    pub fn set_portal(mut self, portal_name: &str) -> Result<Self> {
+        self.portal_id = Some(self.find_portal(portal_name)?);
+        Ok(self)
+    }
+
+    /// Set the Project to which this ZohoClient should refer when making requests.
+    /// All entities requested will be within this Project; if multiple Projects
+    /// are to be queried this can be called a second time to re-use the client.
+    /// The Project names are typically human-friendly strings and should be known
+    /// by the Zoho Project users. Every page of Projects is searched, matching names
+    /// as `set_portal` does.
+    pub fn set_project(mut self, project_name: &str) -> Result<Self> {
+        self.project_id = Some(self.portal_scope()?.find_project(project_name)?);
+        Ok(self)
     }
 
-    /// Construct a Request for retrieving multiple Fora
-    pub fn forums(&self) -> forum::ForumRequest {
-        forum::ForumRequest::new(
-            &self.access_token(),
-            &forum::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+    /// Set the Portal to which this ZohoClient should make requests by its ID, without
+    /// looking it up
+    pub fn set_portal_id(mut self, portal_id: i64) -> Self {
+        self.portal_id = Some(portal_id);
+        self
     }
 
-    /// Construct a Request for retrieving a Comment within a Forum, referencing the
-    /// ID of both the Forum and Comment
-    pub fn forum_comment(&self, forum_id: i64, id: i64) -> forum::comment::CommentRequest {
-        forum::comment::CommentRequest::new(
-            &self.access_token(),
-            &forum::comment::model_path(self.portal_id(), self.project_id(), forum_id),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Comments within a Forum, referencing
-    /// the Forum ID
-    pub fn forum_comments(&self, forum_id: i64) -> forum::comment::CommentRequest {
-        forum::comment::CommentRequest::new(
-            &self.access_token(),
-            &forum::comment::model_path(self.portal_id(), self.project_id(), forum_id),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving a Milestone by ID
-    pub fn milestone(&self, id: i64) -> milestone::MilestoneRequest {
-        milestone::MilestoneRequest::new(
-            &self.access_token(),
-            &milestone::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Milestones
-    pub fn milestones(&self) -> milestone::MilestoneRequest {
-        milestone::MilestoneRequest::new(
-            &self.access_token(),
-            &milestone::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+    /// Set the Project to which this ZohoClient should refer when making requests by its ID,
+    /// without looking it up
+    pub fn set_project_id(mut self, project_id: i64) -> Self {
+        self.project_id = Some(project_id);
+        self
+    }
+
+    /// Scope requests to a Portal, given its ID or name. An ID is used as it is; a name is
+    /// looked up among the Portals this client can access.
+    pub fn portal(&self, portal: impl Into<Ident>) -> Result<PortalScope> {
+        let portal_id = match portal.into() {
+            Ident::Id(id) => id,
+            Ident::Name(name) => self.find_portal(&name)?,
+        };
+        Ok(PortalScope::new(self, portal_id))
+    }
+
+    /// The Portal set with `set_portal` or the builder, failing if there is none
+    pub fn portal_scope(&self) -> Result<PortalScope> {
+        let portal_id = self.portal_id.ok_or_else(|| Error::no_scope("portal"))?;
+        Ok(PortalScope::new(self, portal_id))
+    }
+
+    /// The Project set with `set_project` or the builder, failing if there is none
+    pub fn project_scope(&self) -> Result<ProjectScope> {
+        let project_id = self.project_id.ok_or_else(|| Error::no_scope("project"))?;
+        Ok(self.portal_scope()?.project_id(project_id))
+    }
+
+    fn find_portal(&self, portal_name: &str) -> Result<i64> {
+        find_by_name("portal", portal_name, self.portals().iter_get())
     }
 
     /// Construct a Request for retrieving the available Portals for this Client.
     /// Used when initializing a new Client.
     pub fn portals(&self) -> portal::PortalRequest {
-        portal::PortalRequest::new(&self.access_token())
-    }
-
-    /// Construct a Request for retrieving the users with access to this Client's Portal.
-    pub fn portal_users(&self) -> portal::user::PortalUserRequest {
-        portal::user::PortalUserRequest::new(
-            &self.access_token(),
-            &portal::user::model_path(self.portal_id()),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving a Project by ID
-    pub fn project(&self, id: i64) -> project::ProjectRequest {
-        project::ProjectRequest::new(
-            &self.access_token(),
-            &project::model_path(self.portal_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Projects
-    pub fn projects(&self) -> project::ProjectRequest {
-        project::ProjectRequest::new(
-            &self.access_token(),
-            &project::model_path(self.portal_id()),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving the Users with access to this Client's Project
-    pub fn project_users(&self) -> project::user::ProjectUserRequest {
-        project::user::ProjectUserRequest::new(
-            &self.access_token(),
-            &project::user::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Statuses. There is no singular counterpart.
-    pub fn statuses(&self) -> status::StatusRequest {
-        status::StatusRequest::new(
-            &self.access_token(),
-            &status::model_path(self.portal_id(), self.project_id()),
-        )
-    }
-
-    /// Construct a Request for retrieving a Task by ID
-    pub fn task(&self, id: i64) -> task::TaskRequest {
-        task::TaskRequest::new(
-            &self.access_token(),
-            &task::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Tasks
-    pub fn tasks(&self) -> task::TaskRequest {
-        task::TaskRequest::new(
-            &self.access_token(),
-            &task::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving a Tasklist by ID
-    pub fn tasklist(&self, id: i64) -> tasklist::TasklistRequest {
-        tasklist::TasklistRequest::new(
-            &self.access_token(),
-            &tasklist::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Tasklists
-    pub fn tasklists(&self) -> tasklist::TasklistRequest {
-        tasklist::TasklistRequest::new(
-            &self.access_token(),
-            &tasklist::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving a Task belonging to a specific Tasklist
-    pub fn tasklist_task(
-        &self,
-        tasklist_id: usize,
-        id: i64,
-    ) -> tasklist::task::TasklistTaskRequest {
-        tasklist::task::TasklistTaskRequest::new(
-            &self.access_token(),
-            &tasklist::task::model_path(self.portal_id(), self.project_id(), tasklist_id),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving Tasks belonging to a specific Tasklist
-    pub fn tasklist_tasks(&self, tasklist_id: usize) -> tasklist::task::TasklistTaskRequest {
-        tasklist::task::TasklistTaskRequest::new(
-            &self.access_token(),
-            &tasklist::task::model_path(self.portal_id(), self.project_id(), tasklist_id),
-            None,
-        )
-    }
-
-    /// Construct a Request for retrieving a Timesheet by ID
-    pub fn timesheet(&self, id: i64) -> timesheet::TimesheetRequest {
-        timesheet::TimesheetRequest::new(
-            &self.access_token(),
-            &timesheet::model_path(self.portal_id(), self.project_id()),
-            Some(id),
-        )
-    }
-
-    /// Construct a Request for retrieving multiple Timesheets
-    pub fn timesheets(&self) -> timesheet::TimesheetRequest {
-        timesheet::TimesheetRequest::new(
-            &self.access_token(),
-            &timesheet::model_path(self.portal_id(), self.project_id()),
-            None,
-        )
+        portal::PortalRequest::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ModelRequest;
+    use crate::transport::MockTransport;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn api_domain_identifies_the_data_centre() {
+        let cases = [
+            ("https://www.zohoapis.com", Some(DataCentre::Com)),
+            ("https://www.zohoapis.eu/", Some(DataCentre::Eu)),
+            ("https://www.zohoapis.in", Some(DataCentre::In)),
+            ("https://www.zohoapis.com.au", Some(DataCentre::ComAu)),
+            ("https://www.zohoapis.jp", Some(DataCentre::Jp)),
+            ("https://www.zohoapis.com.cn", Some(DataCentre::ComCn)),
+            ("www.zohoapis.eu", Some(DataCentre::Eu)),
+            ("https://www.zohoapis.example", None),
+        ];
+        for (api_domain, expected) in cases.iter() {
+            assert_eq!(
+                DataCentre::from_api_domain(api_domain),
+                *expected,
+                "{}",
+                api_domain
+            );
+        }
+    }
+
+    #[test]
+    fn data_centre_chooses_both_hosts() {
+        assert_eq!(
+            DataCentre::ComAu.api_base_url(),
+            "https://projectsapi.zoho.com.au/restapi"
+        );
+        assert_eq!(DataCentre::Eu.accounts_url(), "https://accounts.zoho.eu");
+    }
+
+    fn self_client(token: &str) -> (ZohoClientBuilder, MockTransport) {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, token);
+        let builder = ZohoClient::builder("client-id", "client-secret")
+            .self_client("grant-code")
+            .transport(transport.clone());
+        (builder, transport)
+    }
+
+    #[test]
+    fn builder_authorizes_in_the_chosen_data_centre() {
+        let (builder, transport) = self_client(r#"{"access_token": "access"}"#);
+        let client = builder.data_centre(DataCentre::Eu).build().unwrap();
+
+        assert_eq!(
+            transport.requests()[0].url,
+            "https://accounts.zoho.eu/oauth/v2/token"
+        );
+        assert_eq!(client.data_centre(), DataCentre::Eu);
+        assert_eq!(client.api_base_url(), "https://projectsapi.zoho.eu/restapi");
+    }
+
+    #[test]
+    fn api_domain_overrides_the_chosen_data_centre() {
+        let (builder, _) =
+            self_client(r#"{"access_token": "access", "api_domain": "https://www.zohoapis.in"}"#);
+        let client = builder.build().unwrap();
+
+        assert_eq!(client.data_centre(), DataCentre::In);
+        assert!(client
+            .portals()
+            .uri()
+            .starts_with("https://projectsapi.zoho.in/restapi/"));
+    }
+
+    #[test]
+    fn custom_urls_replace_the_data_centre_hosts() {
+        let (builder, transport) = self_client(r#"{"access_token": "access"}"#);
+        let client = builder
+            .accounts_url("http://localhost:9000/")
+            .api_base_url("http://localhost:9001/restapi")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            transport.requests()[0].url,
+            "http://localhost:9000/oauth/v2/token"
+        );
+        assert_eq!(
+            client.portals().uri(),
+            "http://localhost:9001/restapi/portals/"
+        );
+    }
+
+    #[test]
+    fn builder_authorizes_with_the_chosen_scopes_and_redirect() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, r#"{"access_token": "access"}"#);
+        let authorize_url = Arc::new(Mutex::new(String::new()));
+        let seen = authorize_url.clone();
+
+        ZohoClient::builder("client-id", "client-secret")
+            .scopes(Scopes::new(&[
+                "ZohoProjects.portals.READ",
+                "ZohoProjects.tasks.READ",
+            ]))
+            .redirect_uri("http://localhost:9090/callback")
+            .auth_mode(AuthMode::Callback(Arc::new(move |url| {
+                *seen.lock().unwrap() = url.to_owned();
+                "the-code".to_owned()
+            })))
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let authorize_url = url::Url::parse(&authorize_url.lock().unwrap()).unwrap();
+        let query: std::collections::HashMap<_, _> = authorize_url.query_pairs().collect();
+        assert_eq!(
+            query["scope"],
+            "ZohoProjects.portals.READ ZohoProjects.tasks.READ"
+        );
+        assert_eq!(query["redirect_uri"], "http://localhost:9090/callback");
+
+        let body = transport.requests()[0].body.clone().expect("a form body");
+        assert_eq!(
+            body.field("redirect_uri"),
+            Some("http://localhost:9090/callback")
+        );
     }
 }
@@ -1,7 +1,7 @@
 //! Error wrapper module utilising ThisError to unify error handling.
 
 use chrono;
-use reqwest;
+use reqwest::{self, Method, StatusCode};
 use std::env;
 
 use thiserror::Error;
@@ -22,18 +22,41 @@ pub enum Error {
     /// Wrapper for a std::env::VarError
     #[error("Environment variable error: {0}")]
     EnvVar(#[from] env::VarError),
+    /// Wrapper for a std::io::Error, e.g. when reading or writing a token store
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
     /// Failure in JSON parsing
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
     /// Failure in Int parsing
     #[error("Int parsing error: {0}")]
     ParseInt(#[from] std::num::ParseIntError),
+    /// Failure in encoding request data as URL parameters
+    #[error("URL encoding error: {0}")]
+    UrlEncoded(#[from] serde_urlencoded::ser::Error),
+    /// Transport failed to deliver a request or produce a response
+    #[error("Transport error: {0}")]
+    Transport(String),
     /// Server returned no content status code
     #[error("StatusCode::NOCONTENT received when making request")]
     NoContent,
     /// Server returned error code
     #[error("Server returned error code: {0}")]
     ServerError(String),
+    /// Zoho rejected a request, explaining why in the response body
+    #[error("Zoho API error {status} for {method} {url}: {message}{}", code.map(|c| format!(" (code {})", c)).unwrap_or_default())]
+    Api {
+        /// HTTP status of the response
+        status: StatusCode,
+        /// Zoho's own error code, if the body carried one
+        code: Option<i64>,
+        /// Zoho's description of the error, or the status reason if there was none
+        message: String,
+        /// URL the request was made to
+        url: String,
+        /// HTTP method of the request
+        method: Method,
+    },
     /// Client attempted to make a request with invalid HTTP method
     #[error("Disallowed request method: {method} for {model}")]
     DisallowedRequestMethod {
@@ -42,15 +65,97 @@ pub enum Error {
         /// The model on which this method was called
         model: String,
     },
+    /// A read, update or deletion of a single record was requested without its ID
+    #[error("{method} needs the ID of a single record, but none was given for {url}")]
+    MissingRecordId {
+        /// HTTP method of the request
+        method: String,
+        /// URL the request would have been made to
+        url: String,
+    },
+    /// A creation was requested with the ID of an existing record, which Zoho would take as
+    /// an update of that record
+    #[error("{method} creates a record, but was given the ID of an existing one for {url}")]
+    UnexpectedRecordId {
+        /// HTTP method of the request
+        method: String,
+        /// URL the request would have been made to
+        url: String,
+    },
     /// Entity sought by ID returned no results
     #[error("Failed to find entity with ID {0}")]
-    MissingEntityId(usize),
+    MissingEntityId(i64),
     /// Entity sought by name returned no results
     #[error("Failed to find entity with name {0}")]
     MissingEntityName(String),
+    /// Entity sought by name matched more than one record
+    #[error("Name {name} matches more than one entity: {}", candidates.join(", "))]
+    AmbiguousEntityName {
+        /// The name sought
+        name: String,
+        /// Each record it matched, as name and ID
+        candidates: Vec<String>,
+    },
+    /// Request for a single record found nothing
+    #[error("No record found at {0}")]
+    NotFound(String),
+    /// A request needed a Portal or Project which had not been set on the client
+    #[error("No {0} has been set for this client")]
+    NoScope(String),
     /// Model sought en masse returned no results
     #[error("No entries found for {0}")]
     EmptyList(String),
+    /// Failure while obtaining or renewing an OAuth access token
+    #[error("OAuth error ({kind}): {detail}")]
+    OAuth {
+        /// The stage of authorization which failed
+        kind: OAuthErrorKind,
+        /// Further information about the failure
+        detail: String,
+    },
+}
+
+/// Stages of OAuth authorization which may fail, to distinguish e.g. a misconfigured client
+/// from a refused token request.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuthErrorKind {
+    /// An accounts or redirect URL could not be parsed
+    InvalidConfiguration,
+    /// The local listener for the authorization redirect could not be started
+    Listener,
+    /// The authorization redirect could not be read or parsed
+    InvalidRedirect,
+    /// The authorization redirect carried no `code` parameter
+    MissingCode,
+    /// The authorization redirect carried no `state` parameter
+    MissingState,
+    /// The `state` returned did not match the one sent, so the redirect may be forged
+    CsrfMismatch,
+    /// Zoho refused to issue a token, or its response could not be understood
+    TokenRequest,
+    /// A token refresh was attempted with no refresh token held
+    MissingRefreshToken,
+    /// Zoho refused to revoke a token, or its response could not be understood
+    Revocation,
+}
+
+impl std::fmt::Display for OAuthErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str_rep = match self {
+            OAuthErrorKind::InvalidConfiguration => "invalid configuration",
+            OAuthErrorKind::Listener => "redirect listener",
+            OAuthErrorKind::InvalidRedirect => "invalid redirect",
+            OAuthErrorKind::MissingCode => "missing code",
+            OAuthErrorKind::MissingState => "missing state",
+            OAuthErrorKind::CsrfMismatch => "CSRF mismatch",
+            OAuthErrorKind::TokenRequest => "token request",
+            OAuthErrorKind::MissingRefreshToken => "missing refresh token",
+            OAuthErrorKind::Revocation => "token revocation",
+        };
+
+        write!(f, "{}", str_rep)
+    }
 }
 
 impl Error {
@@ -62,6 +167,56 @@ impl Error {
         Error::ServerError(status)
     }
 
+    pub fn api(
+        status: StatusCode,
+        code: Option<i64>,
+        message: &str,
+        url: &str,
+        method: &Method,
+    ) -> Error {
+        Error::Api {
+            status,
+            code,
+            message: message.to_owned(),
+            url: url.to_owned(),
+            method: method.clone(),
+        }
+    }
+
+    /// The HTTP status Zoho responded with, if this error came from a response
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Api { status, .. } => Some(*status),
+            Error::Reqwest(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Whether the requested record, or the Portal or Project it belongs to, doesn't exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::NotFound(_)) || self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Whether Zoho refused the request for exceeding its rate limit, even after any retries
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Whether Zoho rejected the access token, e.g. because it has expired or been revoked
+    pub fn is_auth_expired(&self) -> bool {
+        self.status() == Some(StatusCode::UNAUTHORIZED)
+    }
+
+    /// Whether the authorized user lacks permission for the request, or the token lacks
+    /// the scope for it
+    pub fn is_permission_denied(&self) -> bool {
+        self.status() == Some(StatusCode::FORBIDDEN)
+    }
+
+    pub fn transport(detail: &str) -> Error {
+        Error::Transport(detail.to_owned())
+    }
+
     pub fn disallowed_method(method: &str, model: &str) -> Error {
         Error::DisallowedRequestMethod {
             method: method.to_owned(),
@@ -69,7 +224,21 @@ impl Error {
         }
     }
 
-    pub fn missing_entity_id(id: usize) -> Error {
+    pub fn missing_record_id(method: &str, url: &str) -> Error {
+        Error::MissingRecordId {
+            method: method.to_owned(),
+            url: url.to_owned(),
+        }
+    }
+
+    pub fn unexpected_record_id(method: &str, url: &str) -> Error {
+        Error::UnexpectedRecordId {
+            method: method.to_owned(),
+            url: url.to_owned(),
+        }
+    }
+
+    pub fn missing_entity_id(id: i64) -> Error {
         Error::MissingEntityId(id)
     }
 
@@ -77,7 +246,29 @@ impl Error {
         Error::MissingEntityName(name.to_owned())
     }
 
+    pub fn ambiguous_entity_name(name: &str, candidates: Vec<String>) -> Error {
+        Error::AmbiguousEntityName {
+            name: name.to_owned(),
+            candidates,
+        }
+    }
+
+    pub fn not_found(url: &str) -> Error {
+        Error::NotFound(url.to_owned())
+    }
+
+    pub fn no_scope(scope: &str) -> Error {
+        Error::NoScope(scope.to_owned())
+    }
+
     pub fn empty_entity_list(model: &str) -> Error {
         Error::EmptyList(model.to_owned())
     }
+
+    pub fn oauth(kind: OAuthErrorKind, detail: &str) -> Error {
+        Error::OAuth {
+            kind,
+            detail: detail.to_owned(),
+        }
+    }
 }
@@ -12,11 +12,21 @@
 )]
 #![doc(html_root_url = "https://docs.rs/zohohorrorshow/0.7.4")]
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
 pub mod client;
 pub mod errors;
 pub mod models;
+pub mod paginator;
 pub mod prelude;
+pub mod rate_limit;
 pub mod request;
+pub mod retry;
+pub mod scope;
+pub mod token_store;
+pub mod transport;
 
 mod oauth;
 mod serializers;
+#[cfg(test)]
+mod testing;
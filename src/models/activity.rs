@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use reqwest::Method;
 use std::collections::HashMap;
 
 pub(crate) fn model_path(
@@ -15,12 +18,12 @@ pub(crate) fn model_path(
 pub struct ActivityRequest(RequestDetails);
 
 impl ActivityRequest {
-    pub fn new(access_token: &str, model_path: &str) -> Self {
-        ActivityRequest(RequestDetails::new(access_token, model_path, None))
+    pub fn new(client: &ZohoClient, model_path: &str) -> Self {
+        ActivityRequest(RequestDetails::new(client, model_path, None))
     }
 
     pub fn iter_get(self) -> ActivityIterator {
-        ActivityIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -29,15 +32,19 @@ impl ModelRequest for ActivityRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -46,17 +53,13 @@ impl ModelRequest for ActivityRequest {
 impl RequestParameters for ActivityRequest {
     type ModelCollection = ZohoActivities;
     type NewModel = NewActivity;
+    type UpdateModel = UpdateActivity;
 
-    fn post(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("POST", "Activity"))
-    }
-
-    fn put(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("PUT", "Activity"))
-    }
-
-    fn delete(&self) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("DELETE", "Activity"))
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "Activity")),
+        }
     }
 }
 
@@ -100,6 +103,14 @@ pub struct ZohoActivities {
     pub activities: Vec<Activity>,
 }
 
+impl Collection for ZohoActivities {
+    type Model = Activity;
+
+    fn into_models(self) -> Vec<Activity> {
+        self.activities
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Activity {
     #[serde(rename = "id")]
@@ -125,74 +136,10 @@ pub struct Activity {
 #[derive(Clone, Serialize, Deserialize)]
 pub enum NewActivity {}
 
-#[derive(Debug, Clone)]
-pub struct ActivityIterator {
-    pub items: <Vec<Activity> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: ActivityRequest,
-    pub start_index: usize,
-}
-
-impl ActivityIterator {
-    pub fn new(request: ActivityRequest) -> Self {
-        Self {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-            start_index: 0,
-        }
-    }
-
-    fn range(&self) -> i8 {
-        match self.request.params() {
-            Some(params) => match params.get("range") {
-                Some(range_string) => range_string.parse::<i8>().unwrap_or(100),
-                None => 100,
-            },
-            None => 100,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Activity>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(activity) = self.items.next() {
-            return Ok(Some(activity));
-        }
-
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_activities = self
-            .request
-            .clone()
-            .filter(Filter::Index(self.start_index))
-            .get()?;
-
-        if let Some(activity_list) = returned_activities {
-            self.last_full = activity_list.activities.len() as i8 == self.range();
-
-            self.start_index += activity_list.activities.len();
-
-            self.items = activity_list.activities.into_iter();
-
-            Ok(self.items.next())
-        } else {
-            Ok(None)
-        }
-    }
-}
-
-impl Iterator for ActivityIterator {
-    type Item = Result<Activity>;
+/// Unconstructable enum representing a theoretical change to an Activity.
+/// Zoho Projects does not permit changing Activities through the API.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum UpdateActivity {}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => Some(Err(err)),
-        }
-    }
-}
+/// Iterator across every Activity, fetching them from Zoho a page at a time
+pub type ActivityIterator = Paginator<ActivityRequest>;
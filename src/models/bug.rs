@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
-use tracing::warn;
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::models::multi_filter_format;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::models::{multi_filter_format, OwningProject};
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
+use reqwest::Method;
 use std::collections::HashMap;
 
 pub(crate) fn model_path(
@@ -14,16 +16,20 @@ pub(crate) fn model_path(
     format!("portal/{}/projects/{}/bugs/", portal, project)
 }
 
+pub(crate) fn my_bugs_path(portal: impl std::fmt::Display) -> String {
+    format!("portal/{}/mybugs/", portal)
+}
+
 #[derive(Clone, Debug)]
 pub struct BugRequest(RequestDetails);
 
 impl BugRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        BugRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        BugRequest(RequestDetails::new(client, model_path, id))
     }
 
     pub fn iter_get(self) -> BugIterator {
-        BugIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -32,15 +38,19 @@ impl ModelRequest for BugRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -49,6 +59,58 @@ impl ModelRequest for BugRequest {
 impl RequestParameters for BugRequest {
     type ModelCollection = ZohoBugs;
     type NewModel = NewBug;
+    type UpdateModel = UpdateBug;
+}
+
+/// Request for the Bugs of the authorized user across every Project in a Portal. Accepts the
+/// same Filters as a BugRequest. Each Bug returned carries the Project it belongs to.
+#[derive(Clone, Debug)]
+pub struct MyBugRequest(RequestDetails);
+
+impl MyBugRequest {
+    pub fn new(client: &ZohoClient, model_path: &str) -> Self {
+        MyBugRequest(RequestDetails::new(client, model_path, None))
+    }
+
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
+    }
+}
+
+impl ModelRequest for MyBugRequest {
+    fn uri(&self) -> String {
+        self.0.uri()
+    }
+
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
+    fn params(&self) -> Option<HashMap<String, String>> {
+        self.0.params()
+    }
+
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
+    }
+
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
+        self.0 = self.0.filter(&param);
+        self
+    }
+}
+
+impl RequestParameters for MyBugRequest {
+    type ModelCollection = ZohoBugs;
+    type NewModel = NewBug;
+    type UpdateModel = UpdateBug;
+
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "MyBug")),
+        }
+    }
 }
 
 pub enum Flag {
@@ -139,6 +201,14 @@ pub struct ZohoBugs {
     pub bugs: Vec<Bug>,
 }
 
+impl Collection for ZohoBugs {
+    type Model = Bug;
+
+    fn into_models(self) -> Vec<Bug> {
+        self.bugs
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct Bug {
     #[serde(rename = "module")]
@@ -177,6 +247,9 @@ pub struct Bug {
     pub created_time: String,
     #[serde(rename = "key")]
     pub key: String,
+    /// The Project this Bug belongs to; only present on Bugs from `my_bugs`
+    #[serde(rename = "project", default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<OwningProject>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -185,6 +258,37 @@ pub struct NewBug {
     pub work_category: String,
 }
 
+/// Changes to make to an existing Bug; only the fields which are set are sent.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateBug {
+    #[serde(rename = "title", skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "assignee", skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<i64>,
+    /// "Internal" or "External"; see [`Flag::value`](enum.Flag.html#method.value)
+    #[serde(rename = "flag", skip_serializing_if = "Option::is_none")]
+    pub flag: Option<String>,
+    #[serde(rename = "status_id", skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<i64>,
+    #[serde(rename = "classification_id", skip_serializing_if = "Option::is_none")]
+    pub classification_id: Option<i64>,
+    #[serde(rename = "severity_id", skip_serializing_if = "Option::is_none")]
+    pub severity_id: Option<i64>,
+    #[serde(rename = "reproducible_id", skip_serializing_if = "Option::is_none")]
+    pub reproducible_id: Option<i64>,
+    #[serde(rename = "module_id", skip_serializing_if = "Option::is_none")]
+    pub module_id: Option<i64>,
+    #[serde(rename = "milestone_id", skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<i64>,
+    /// Format: MM-DD-YYYY
+    #[serde(rename = "due_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(rename = "CHAR4", skip_serializing_if = "Option::is_none")]
+    pub work_category: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct IntClassification {
     #[serde(rename = "id")]
@@ -284,91 +388,5 @@ impl std::fmt::Display for StatusType {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct BugIterator {
-    pub items: <Vec<Bug> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: BugRequest,
-    pub start_index: usize,
-}
-
-impl BugIterator {
-    pub fn new(request: BugRequest) -> BugIterator {
-        BugIterator {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-            start_index: 0,
-        }
-    }
-
-    fn range(&self) -> i8 {
-        match self.request.params() {
-            Some(params) => match params.get("range") {
-                Some(range_string) => range_string.parse::<i8>().unwrap_or(100),
-                None => 100,
-            },
-            None => 100,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Bug>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(bug) = self.items.next() {
-            return Ok(Some(bug));
-        }
-
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_tickets = self
-            .request
-            .clone()
-            .filter(Filter::Index(self.start_index))
-            .get();
-
-        match returned_tickets {
-            Ok(Some(ticket_list)) => {
-                if ticket_list.bugs.is_empty() {
-                    self.last_full = false;
-                    return Ok(None);
-                }
-
-                self.last_full = ticket_list.bugs.len() as i8 == self.range();
-
-                self.start_index += ticket_list.bugs.len();
-
-                self.items = ticket_list.bugs.into_iter();
-
-                Ok(self.items.next())
-            }
-            Ok(None) => {
-                self.last_full = false;
-
-                Ok(None)
-            }
-            Err(err) => {
-                self.last_full = false;
-                Err(err)
-            }
-        }
-    }
-}
-
-impl Iterator for BugIterator {
-    type Item = Result<Bug>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => {
-                warn!("Fetching Bugs from Zoho experienced an error: {}", err);
-                None
-            }
-        }
-    }
-}
+/// Iterator across every Bug, fetching them from Zoho a page at a time
+pub type BugIterator = Paginator<BugRequest>;
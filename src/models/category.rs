@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
+use reqwest::Method;
 use std::collections::HashMap;
 
 pub(crate) fn model_path(
@@ -16,12 +19,12 @@ pub(crate) fn model_path(
 pub struct CategoryRequest(RequestDetails);
 
 impl CategoryRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        CategoryRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        CategoryRequest(RequestDetails::new(client, model_path, id))
     }
 
     pub fn iter_get(self) -> CategoryIterator {
-        CategoryIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -30,15 +33,20 @@ impl ModelRequest for CategoryRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(self, _param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
+        self.0 = self.0.filter(&param);
         self
     }
 }
@@ -46,9 +54,13 @@ impl ModelRequest for CategoryRequest {
 impl RequestParameters for CategoryRequest {
     type ModelCollection = ZohoCategories;
     type NewModel = NewCategory;
+    type UpdateModel = UpdateCategory;
 
-    fn put(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("PUT", "Category"))
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET | Method::POST | Method::DELETE => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "Category")),
+        }
     }
 }
 
@@ -64,6 +76,14 @@ pub struct ZohoCategories {
     pub categories: Vec<Category>,
 }
 
+impl Collection for ZohoCategories {
+    type Model = Category;
+
+    fn into_models(self) -> Vec<Category> {
+        self.categories
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Category {
     #[serde(rename = "id", deserialize_with = "from_str")]
@@ -78,55 +98,10 @@ pub struct NewCategory {
     pub name: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct CategoryIterator {
-    pub items: <Vec<Category> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: CategoryRequest,
-}
-
-impl CategoryIterator {
-    pub fn new(request: CategoryRequest) -> Self {
-        Self {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Category>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(category) = self.items.next() {
-            return Ok(Some(category));
-        }
+/// Unconstructable enum representing a theoretical change to a Category.
+/// Zoho Projects does not permit changing Categories through the API.
+#[derive(Clone, Debug, Serialize)]
+pub enum UpdateCategory {}
 
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_categories = self.request.clone().get()?;
-
-        if let Some(category_list) = returned_categories {
-            self.last_full = category_list.categories.len() as i8 == 100;
-            self.items = category_list.categories.into_iter();
-
-            Ok(self.items.next())
-        } else {
-            Ok(None)
-        }
-    }
-}
-
-impl Iterator for CategoryIterator {
-    type Item = Result<Category>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => Some(Err(err)),
-        }
-    }
-}
+/// Iterator across every Category, fetching them from Zoho a page at a time
+pub type CategoryIterator = Paginator<CategoryRequest>;
@@ -1,4 +1,6 @@
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::{from_str, join_ids};
 use serde::ser::{self, Serializer};
 use serde::{Deserialize, Serialize};
@@ -15,8 +17,14 @@ pub(crate) fn model_path(
 pub struct EventRequest(RequestDetails);
 
 impl EventRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        EventRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        EventRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -25,15 +33,19 @@ impl ModelRequest for EventRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -42,6 +54,7 @@ impl ModelRequest for EventRequest {
 impl RequestParameters for EventRequest {
     type ModelCollection = ZohoEvents;
     type NewModel = NewEvent;
+    type UpdateModel = UpdateEvent;
 }
 
 pub enum Filter {
@@ -83,6 +96,14 @@ pub struct ZohoEvents {
     pub events: Vec<Event>,
 }
 
+impl Collection for ZohoEvents {
+    type Model = Event;
+
+    fn into_models(self) -> Vec<Event> {
+        self.events
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Event {
     #[serde(rename = "id")]
@@ -130,6 +151,35 @@ pub struct NewEvent {
     pub location: Option<String>,
 }
 
+/// Changes to make to an existing Event; only the fields which are set are sent.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct UpdateEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hour: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minutes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ampm: Option<AmPm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_hour: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_mins: Option<String>,
+    #[serde(serialize_with = "join_ids", skip_serializing_if = "Option::is_none")]
+    pub participants: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remind_before: Option<RemindBefore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<Repeat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nooftimes_repeat: Option<NumRepeat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 pub enum AmPm {
     #[default]
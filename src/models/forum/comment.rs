@@ -1,5 +1,10 @@
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters, ZohoRequest};
+use crate::paginator::Paginator;
+use crate::request::{
+    Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters, ZohoRequest,
+};
+use crate::transport::Upload;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -19,12 +24,12 @@ pub(crate) fn model_path(
 pub struct CommentRequest(RequestDetails);
 
 impl CommentRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        CommentRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        CommentRequest(RequestDetails::new(client, model_path, id))
     }
 
     pub fn iter_get(self) -> CommentIterator {
-        CommentIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -33,15 +38,19 @@ impl ModelRequest for CommentRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -50,29 +59,26 @@ impl ModelRequest for CommentRequest {
 impl RequestParameters for CommentRequest {
     type ModelCollection = ZohoComments;
     type NewModel = NewComment;
+    type UpdateModel = UpdateComment;
+
+    fn uploads(&self, data: &Self::NewModel) -> Vec<Upload> {
+        data.files.clone()
+    }
 }
 
 impl CommentRequest {
-    pub fn mark_best(&self) -> Result<Option<Response>> {
+    pub fn mark_best(&self) -> Result<Response> {
         let mut url = self.uri();
         url.push_str("markbestanswer");
 
-        ZohoRequest::<NewComment>::new(Method::POST, &url, None, self.access_token(), self.params())
-            .send()
+        ZohoRequest::new(Method::POST, &url, self.params()).send(self.client())
     }
 
-    pub fn unmark_best(&self) -> Result<Option<Response>> {
+    pub fn unmark_best(&self) -> Result<Response> {
         let mut url = self.uri();
         url.push_str("markbestanswer");
 
-        ZohoRequest::<NewComment>::new(
-            Method::DELETE,
-            &url,
-            None,
-            self.access_token(),
-            self.params(),
-        )
-        .send()
+        ZohoRequest::new(Method::DELETE, &url, self.params()).send(self.client())
     }
 }
 
@@ -101,9 +107,17 @@ impl std::fmt::Display for Filter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ZohoComments {
-    comments: Vec<Comment>,
+    pub comments: Vec<Comment>,
+}
+
+impl Collection for ZohoComments {
+    type Model = Comment;
+
+    fn into_models(self) -> Vec<Comment> {
+        self.comments
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -131,7 +145,16 @@ pub struct NewComment {
     pub comment_type: String,
     pub content: String,
     pub parent_id: i64,
-    //TODO(Xymist): uploadfile
+    /// Files to attach to the comment
+    #[serde(skip)]
+    pub files: Vec<Upload>,
+}
+
+/// Changes to make to an existing Comment; only the fields which are set are sent.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateComment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -147,74 +170,5 @@ pub struct Response {
     response: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct CommentIterator {
-    pub items: <Vec<Comment> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: CommentRequest,
-    pub start_index: usize,
-}
-
-impl CommentIterator {
-    pub fn new(request: CommentRequest) -> CommentIterator {
-        CommentIterator {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-            start_index: 0,
-        }
-    }
-
-    fn range(&self) -> i8 {
-        match self.request.params() {
-            Some(params) => match params.get("range") {
-                Some(range_string) => range_string.parse::<i8>().unwrap_or(100),
-                None => 100,
-            },
-            None => 100,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Comment>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(comment) = self.items.next() {
-            return Ok(Some(comment));
-        }
-
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_comments = self
-            .request
-            .clone()
-            .filter(Filter::Index(self.start_index))
-            .get()?;
-
-        if let Some(comment_list) = returned_comments {
-            self.last_full = comment_list.comments.len() as i8 == self.range();
-
-            self.start_index += comment_list.comments.len();
-
-            self.items = comment_list.comments.into_iter();
-
-            Ok(self.items.next())
-        } else {
-            Ok(None)
-        }
-    }
-}
-
-impl Iterator for CommentIterator {
-    type Item = Result<Comment>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => Some(Err(err)),
-        }
-    }
-}
+/// Iterator across every Comment, fetching them from Zoho a page at a time
+pub type CommentIterator = Paginator<CommentRequest>;
@@ -1,5 +1,10 @@
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters, ZohoRequest};
+use crate::paginator::Paginator;
+use crate::request::{
+    Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters, ZohoRequest,
+};
+use crate::transport::Upload;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -17,12 +22,12 @@ pub(crate) fn model_path(
 pub struct ForumRequest(RequestDetails);
 
 impl ForumRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        ForumRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        ForumRequest(RequestDetails::new(client, model_path, id))
     }
 
     pub fn iter_get(self) -> ForumIterator {
-        ForumIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -31,15 +36,20 @@ impl ModelRequest for ForumRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(self, _param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
+        self.0 = self.0.filter(&param);
         self
     }
 }
@@ -47,23 +57,26 @@ impl ModelRequest for ForumRequest {
 impl RequestParameters for ForumRequest {
     type ModelCollection = ZohoForums;
     type NewModel = NewForum;
+    type UpdateModel = UpdateForum;
+
+    fn uploads(&self, data: &Self::NewModel) -> Vec<Upload> {
+        data.files.clone()
+    }
 }
 
 impl ForumRequest {
-    pub fn follow(&self) -> Result<Option<Response>> {
+    pub fn follow(&self) -> Result<Response> {
         let mut url = self.uri();
         url.push_str("follow");
 
-        ZohoRequest::<NewForum>::new(Method::POST, &url, None, self.access_token(), self.params())
-            .send()
+        ZohoRequest::new(Method::POST, &url, self.params()).send(self.client())
     }
 
-    pub fn unfollow(&self) -> Result<Option<Response>> {
+    pub fn unfollow(&self) -> Result<Response> {
         let mut url = self.uri();
         url.push_str("unfollow");
 
-        ZohoRequest::<NewForum>::new(Method::POST, &url, None, self.access_token(), self.params())
-            .send()
+        ZohoRequest::new(Method::POST, &url, self.params()).send(self.client())
     }
 }
 
@@ -73,6 +86,14 @@ pub struct ZohoForums {
     pub forums: Vec<Forum>,
 }
 
+impl Collection for ZohoForums {
+    type Model = Forum;
+
+    fn into_models(self) -> Vec<Forum> {
+        self.forums
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Forum {
     #[serde(rename = "id")]
@@ -121,7 +142,28 @@ pub struct NewForum {
     pub announcement: bool,
     #[serde(rename = "category_id")]
     pub category_id: i64,
-    //TODO(Xymist): notify, flag, type, file
+    /// Files to attach to the post
+    #[serde(skip)]
+    pub files: Vec<Upload>,
+    //TODO(Xymist): notify, flag, type
+}
+
+/// Changes to make to an existing Forum post; only the fields which are set are sent.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateForum {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "content", skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(rename = "is_sticky_post", skip_serializing_if = "Option::is_none")]
+    pub sticky: Option<bool>,
+    #[serde(
+        rename = "is_announcement_post",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub announcement: Option<bool>,
+    #[serde(rename = "category_id", skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i64>,
 }
 
 #[allow(dead_code)]
@@ -130,56 +172,5 @@ pub struct Response {
     response: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct ForumIterator {
-    pub items: <Vec<Forum> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: ForumRequest,
-}
-
-impl ForumIterator {
-    pub fn new(request: ForumRequest) -> ForumIterator {
-        ForumIterator {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Forum>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(forum) = self.items.next() {
-            return Ok(Some(forum));
-        }
-
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_forums = self.request.clone().get()?;
-
-        if let Some(forum_list) = returned_forums {
-            self.last_full = forum_list.forums.len() as i8 == 100;
-
-            self.items = forum_list.forums.into_iter();
-
-            Ok(self.items.next())
-        } else {
-            Ok(None)
-        }
-    }
-}
-
-impl Iterator for ForumIterator {
-    type Item = Result<Forum>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => Some(Err(err)),
-        }
-    }
-}
+/// Iterator across every Forum, fetching them from Zoho a page at a time
+pub type ForumIterator = Paginator<ForumRequest>;
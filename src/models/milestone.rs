@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
 use std::collections::HashMap;
 
@@ -15,8 +17,14 @@ pub(crate) fn model_path(
 pub struct MilestoneRequest(RequestDetails);
 
 impl MilestoneRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        MilestoneRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        MilestoneRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -25,15 +33,19 @@ impl ModelRequest for MilestoneRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -42,6 +54,7 @@ impl ModelRequest for MilestoneRequest {
 impl RequestParameters for MilestoneRequest {
     type ModelCollection = ZohoMilestones;
     type NewModel = NewMilestone;
+    type UpdateModel = UpdateMilestone;
 }
 
 pub enum Filter {
@@ -153,6 +166,14 @@ pub struct ZohoMilestones {
     pub milestones: Vec<Milestone>,
 }
 
+impl Collection for ZohoMilestones {
+    type Model = Milestone;
+
+    fn into_models(self) -> Vec<Milestone> {
+        self.milestones
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Milestone {
     #[serde(rename = "id")]
@@ -205,3 +226,20 @@ pub struct NewMilestone {
     owner: i64,
     flag: String,
 }
+
+/// Changes to make to an existing Milestone; only the fields which are set are sent.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct UpdateMilestone {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Format: MM-DD-YYYY
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Format: MM-DD-YYYY
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<Flag>,
+}
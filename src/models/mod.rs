@@ -16,6 +16,18 @@ pub mod task;
 pub mod tasklist;
 pub mod timesheet;
 
+use serde::{Deserialize, Serialize};
+
+/// The Project a record belongs to, included with records from portal-wide listings such as
+/// `PortalScope::my_tasks` which span several Projects.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct OwningProject {
+    #[serde(rename = "id")]
+    pub id: i64,
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
 fn multi_filter_format(elements: &[impl std::fmt::Display]) -> String {
     let element_string = elements
         .iter()
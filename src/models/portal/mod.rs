@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
+use reqwest::Method;
 use std::collections::HashMap;
 
 pub mod user;
@@ -17,8 +20,14 @@ pub struct PortalRequest(RequestDetails);
 
 impl PortalRequest {
     /// Constructor for a new PortalRequest
-    pub(crate) fn new(access_token: &str) -> Self {
-        PortalRequest(RequestDetails::new(access_token, &model_path(), None))
+    pub(crate) fn new(client: &ZohoClient) -> Self {
+        PortalRequest(RequestDetails::new(client, &model_path(), None))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -27,15 +36,19 @@ impl ModelRequest for PortalRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(self, _param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(self, _param: impl FilterOptions + std::fmt::Display) -> Self {
         self
     }
 }
@@ -43,17 +56,13 @@ impl ModelRequest for PortalRequest {
 impl RequestParameters for PortalRequest {
     type ModelCollection = ZohoPortals;
     type NewModel = NewPortal;
+    type UpdateModel = UpdatePortal;
 
-    fn post(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("POST", "Portal"))
-    }
-
-    fn put(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("PUT", "Portal"))
-    }
-
-    fn delete(&self) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("DELETE", "Portal"))
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "Portal")),
+        }
     }
 }
 
@@ -62,6 +71,11 @@ impl RequestParameters for PortalRequest {
 #[derive(Debug, Serialize, Clone)]
 pub enum NewPortal {}
 
+/// Unconstructable enum representing a theoretical change to a Portal record.
+/// The Zoho Projects API does not permit changing Portals.
+#[derive(Debug, Serialize, Clone)]
+pub enum UpdatePortal {}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct ZohoPortals {
     #[serde(rename = "login_id", deserialize_with = "from_str")]
@@ -70,10 +84,20 @@ pub struct ZohoPortals {
     pub portals: Vec<Portal>,
 }
 
+impl Collection for ZohoPortals {
+    type Model = Portal;
+
+    fn into_models(self) -> Vec<Portal> {
+        self.portals
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Portal {
     #[serde(rename = "id")]
     pub id: i64,
+    #[serde(rename = "id_string")]
+    pub id_string: String,
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "default")]
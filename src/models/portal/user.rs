@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
 use std::collections::HashMap;
 use std::fmt;
@@ -13,8 +15,14 @@ pub(crate) fn model_path(portal: impl std::fmt::Display) -> String {
 pub struct PortalUserRequest(RequestDetails);
 
 impl PortalUserRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        PortalUserRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        PortalUserRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -23,15 +31,19 @@ impl ModelRequest for PortalUserRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -40,6 +52,7 @@ impl ModelRequest for PortalUserRequest {
 impl RequestParameters for PortalUserRequest {
     type ModelCollection = ZohoUsers;
     type NewModel = NewUser;
+    type UpdateModel = UpdateUser;
 }
 
 // There is exactly one variant of this, "usertype", so the usual enum is
@@ -76,6 +89,14 @@ pub struct ZohoUsers {
     pub users: Vec<User>,
 }
 
+impl Collection for ZohoUsers {
+    type Model = User;
+
+    fn into_models(self) -> Vec<User> {
+        self.users
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct User {
     #[serde(rename = "id", deserialize_with = "from_str")]
@@ -100,6 +121,15 @@ pub struct NewUser {
     pub rate: String,
 }
 
+/// Changes to make to an existing User; only the fields which are set are sent.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateUser {
+    #[serde(rename = "role", skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    #[serde(rename = "rate", skip_serializing_if = "Option::is_none")]
+    pub rate: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Role {
     Manager,
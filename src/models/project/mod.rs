@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
 use std::collections::HashMap;
 
@@ -14,8 +16,14 @@ pub(crate) fn model_path(portal: impl std::fmt::Display) -> String {
 pub struct ProjectRequest(RequestDetails);
 
 impl ProjectRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        ProjectRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        ProjectRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -24,15 +32,19 @@ impl ModelRequest for ProjectRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -41,6 +53,7 @@ impl ModelRequest for ProjectRequest {
 impl RequestParameters for ProjectRequest {
     type ModelCollection = ZohoProjects;
     type NewModel = NewProject;
+    type UpdateModel = UpdateProject;
 }
 
 pub enum Filter {
@@ -90,12 +103,42 @@ pub struct NewProject {
     strict_project: String,
 }
 
+/// Changes to make to an existing Project; only the fields which are set are sent.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct UpdateProject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Format: MM-DD-YYYY
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Format: MM-DD-YYYY
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_project: Option<String>,
+    /// "active" or "archived"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct ZohoProjects {
     #[serde(rename = "projects")]
     pub projects: Vec<Project>,
 }
 
+impl Collection for ZohoProjects {
+    type Model = Project;
+
+    fn into_models(self) -> Vec<Project> {
+        self.projects
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Project {
     #[serde(rename = "custom_fields")]
@@ -1,3 +1,5 @@
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
 use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use std::collections::HashMap;
 
@@ -12,8 +14,14 @@ pub(crate) fn model_path(
 pub struct ProjectUserRequest(RequestDetails);
 
 impl ProjectUserRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        ProjectUserRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        ProjectUserRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -22,15 +30,19 @@ impl ModelRequest for ProjectUserRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -39,4 +51,5 @@ impl ModelRequest for ProjectUserRequest {
 impl RequestParameters for ProjectUserRequest {
     type ModelCollection = crate::models::portal::user::ZohoUsers;
     type NewModel = crate::models::portal::user::NewUser;
+    type UpdateModel = crate::models::portal::user::UpdateUser;
 }
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use reqwest::Method;
 use std::collections::HashMap;
 
 pub(crate) fn model_path(
@@ -15,8 +18,14 @@ pub(crate) fn model_path(
 pub struct StatusRequest(RequestDetails);
 
 impl StatusRequest {
-    pub fn new(access_token: &str, model_path: &str) -> Self {
-        StatusRequest(RequestDetails::new(access_token, model_path, None))
+    pub fn new(client: &ZohoClient, model_path: &str) -> Self {
+        StatusRequest(RequestDetails::new(client, model_path, None))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -25,15 +34,19 @@ impl ModelRequest for StatusRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -42,13 +55,13 @@ impl ModelRequest for StatusRequest {
 impl RequestParameters for StatusRequest {
     type ModelCollection = ZohoStatuses;
     type NewModel = NewStatus;
+    type UpdateModel = UpdateStatus;
 
-    fn put(&self, _data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("PUT", "Status"))
-    }
-
-    fn delete(&self) -> Result<Option<Self::ModelCollection>> {
-        Err(Error::disallowed_method("DELETE", "Status"))
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET | Method::POST => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "Status")),
+        }
     }
 }
 
@@ -83,6 +96,14 @@ pub struct ZohoStatuses {
     pub statuses: Vec<Status>,
 }
 
+impl Collection for ZohoStatuses {
+    type Model = Status;
+
+    fn into_models(self) -> Vec<Status> {
+        self.statuses
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Status {
     #[serde(rename = "id")]
@@ -105,6 +126,11 @@ pub struct NewStatus {
     content: String,
 }
 
+/// Unconstructable enum representing a theoretical change to a Status.
+/// Zoho Projects does not permit changing Statuses through the API.
+#[derive(Clone, Debug, Serialize)]
+pub enum UpdateStatus {}
+
 impl NewStatus {
     pub fn new(content: &str) -> Self {
         NewStatus {
@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
-use tracing::warn;
 
+use crate::client::ZohoClient;
 use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
-use crate::serializers::from_str;
-use std::collections::HashMap;
+use crate::models::OwningProject;
+use crate::paginator::{Cursor, Paginator};
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::serializers::{from_str, join_ids, json_string, zoho_date};
+use chrono::NaiveDate;
+use reqwest::Method;
+use std::collections::{BTreeMap, HashMap};
+
+// Parents whose subtasks a TaskIterator fetches at once, unless told otherwise.
+const DEFAULT_SUBTASK_CONCURRENCY: usize = 4;
 
 pub(crate) fn model_path(
     portal: impl std::fmt::Display,
@@ -13,6 +20,10 @@ pub(crate) fn model_path(
     format!("portal/{}/projects/{}/tasks/", portal, project)
 }
 
+pub(crate) fn my_tasks_path(portal: impl std::fmt::Display) -> String {
+    format!("portal/{}/mytasks/", portal)
+}
+
 #[derive(Clone, Debug)]
 pub struct TaskRequest {
     details: RequestDetails,
@@ -21,9 +32,9 @@ pub struct TaskRequest {
 
 impl TaskRequest {
     /// Generate a new TaskRequest
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
         TaskRequest {
-            details: RequestDetails::new(access_token, model_path, id),
+            details: RequestDetails::new(client, model_path, id),
             with_subtasks: false,
         }
     }
@@ -38,6 +49,20 @@ impl TaskRequest {
     pub fn iter_get(self) -> TaskIterator {
         TaskIterator::new(self)
     }
+
+    // A request for the subtasks of the Task with this ID, which lists them even where this
+    // request is for that single Task.
+    pub(crate) fn subtasks_of(&self, id: i64) -> Self {
+        let mut request = self.clone();
+        request.details.model_path = format!("{}{}/subtasks/", self.details.model_path, id);
+        request.details.id = None;
+        request
+    }
+
+    /// Return a TaskIterator continuing from a Cursor saved from an earlier one
+    pub fn resume(self, cursor: Cursor) -> TaskIterator {
+        TaskIterator::new(self).resume(cursor)
+    }
 }
 
 impl ModelRequest for TaskRequest {
@@ -45,15 +70,19 @@ impl ModelRequest for TaskRequest {
         self.details.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.details.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.details.params()
     }
 
-    fn access_token(&self) -> String {
-        self.details.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.details.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.details = self.details.filter(&param);
         self
     }
@@ -62,6 +91,70 @@ impl ModelRequest for TaskRequest {
 impl RequestParameters for TaskRequest {
     type ModelCollection = ZohoTasks;
     type NewModel = NewTask;
+    type UpdateModel = UpdateTask;
+
+    fn parent_id(&self, task: &Task) -> Option<i64> {
+        Some(task.id).filter(|_| self.with_subtasks && task.subtasks)
+    }
+
+    fn nested(&self, parent_id: i64) -> Option<Self> {
+        Some(self.subtasks_of(parent_id))
+    }
+}
+
+/// Request for the Tasks of the authorized user across every Project in a Portal. Accepts the
+/// same Filters as a TaskRequest, other than TasklistId and CustomStatus, which are specific
+/// to a Project. Each Task returned carries the Project it belongs to.
+#[derive(Clone, Debug)]
+pub struct MyTaskRequest(RequestDetails);
+
+impl MyTaskRequest {
+    /// Generate a new MyTaskRequest
+    pub fn new(client: &ZohoClient, model_path: &str) -> Self {
+        MyTaskRequest(RequestDetails::new(client, model_path, None))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
+    }
+}
+
+impl ModelRequest for MyTaskRequest {
+    fn uri(&self) -> String {
+        self.0.uri()
+    }
+
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
+    fn params(&self) -> Option<HashMap<String, String>> {
+        self.0.params()
+    }
+
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
+    }
+
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
+        self.0 = self.0.filter(&param);
+        self
+    }
+}
+
+impl RequestParameters for MyTaskRequest {
+    type ModelCollection = ZohoTasks;
+    type NewModel = NewTask;
+    type UpdateModel = UpdateTask;
+
+    fn permits(&self, method: &Method) -> Result<()> {
+        match *method {
+            Method::GET => Ok(()),
+            _ => Err(Error::disallowed_method(method.as_str(), "MyTask")),
+        }
+    }
 }
 
 /// Various fields by which a Zoho Task API response may be filtered
@@ -73,7 +166,7 @@ pub enum Filter {
     /// The owner/creator of the Tasks to be retrieved
     Owner(i64),
     /// The Priority of Tasks to be retrieved
-    Priority(String),
+    Priority(Priority),
     /// The ID of the Tasklist to which the retrieved Tasks must belong
     TasklistId(i64),
     /// The CustomStatus of Tasks to be retrieved
@@ -107,7 +200,7 @@ impl std::fmt::Display for Filter {
             Filter::Index(index) => index.to_string(),
             Filter::Range(range) => range.to_string(),
             Filter::Owner(owner) => owner.to_string(),
-            Filter::Priority(priority) => priority.clone(),
+            Filter::Priority(priority) => priority.to_string(),
             Filter::TasklistId(tasklist_id) => tasklist_id.to_string(),
             Filter::CustomStatus(custom_status) => custom_status.clone(),
             Filter::Status(status) => status.to_string(),
@@ -175,10 +268,257 @@ pub struct ZohoTasks {
     pub tasks: Vec<Task>,
 }
 
+impl Collection for ZohoTasks {
+    type Model = Task;
+
+    fn into_models(self) -> Vec<Task> {
+        self.tasks
+    }
+}
+
+/// The fields with which a Task may be created; only `name` is required. Most easily put
+/// together with `NewTask::builder`.
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct NewTask {
     #[serde(rename = "name")]
     pub name: String,
+    /// IDs of the users to assign the Task to
+    #[serde(
+        rename = "person_responsible",
+        serialize_with = "join_ids",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub person_responsible: Option<Vec<i64>>,
+    #[serde(rename = "tasklist_id", skip_serializing_if = "Option::is_none")]
+    pub tasklist_id: Option<i64>,
+    #[serde(
+        rename = "start_date",
+        serialize_with = "zoho_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_date: Option<NaiveDate>,
+    #[serde(
+        rename = "end_date",
+        serialize_with = "zoho_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_date: Option<NaiveDate>,
+    /// Length of the Task, in `duration_type` units
+    #[serde(rename = "duration", skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(rename = "duration_type", skip_serializing_if = "Option::is_none")]
+    pub duration_type: Option<DurationUnit>,
+    #[serde(rename = "priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "rate_per_hour", skip_serializing_if = "Option::is_none")]
+    pub rate_per_hour: Option<f64>,
+    /// Values for the Project's custom fields, keyed by column name, e.g. "UDF_CHAR1"
+    #[serde(
+        rename = "custom_fields",
+        serialize_with = "json_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub custom_fields: Option<BTreeMap<String, String>>,
+}
+
+impl NewTask {
+    /// Start building a NewTask with the given name
+    pub fn builder(name: &str) -> NewTaskBuilder {
+        NewTaskBuilder {
+            task: NewTask {
+                name: name.to_owned(),
+                ..NewTask::default()
+            },
+        }
+    }
+}
+
+/// Builder for a NewTask, created with `NewTask::builder`.
+///
+/// ```no_run
+/// # use zohohorrorshow::prelude::*;
+/// # use zohohorrorshow::models::task::{NewTask, Priority};
+/// # fn run(project: ProjectScope) -> zohohorrorshow::errors::Result<()> {
+/// let due = chrono::NaiveDate::from_ymd_opt(2024, 3, 29).expect("valid date");
+/// let task = NewTask::builder("Write release notes")
+///     .owners(&[170876000000093002])
+///     .tasklist(170876000000095005)
+///     .priority(Priority::High)
+///     .due(due)
+///     .build();
+/// project.tasks().post(task)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NewTaskBuilder {
+    task: NewTask,
+}
+
+impl NewTaskBuilder {
+    /// Assign the Task to the users with these IDs
+    pub fn owners(mut self, owners: &[i64]) -> Self {
+        self.task.person_responsible = Some(owners.to_vec());
+        self
+    }
+
+    /// Create the Task within the Tasklist with this ID
+    pub fn tasklist(mut self, tasklist_id: i64) -> Self {
+        self.task.tasklist_id = Some(tasklist_id);
+        self
+    }
+
+    /// Set the date on which work on the Task starts
+    pub fn start(mut self, start_date: NaiveDate) -> Self {
+        self.task.start_date = Some(start_date);
+        self
+    }
+
+    /// Set the date by which the Task is due
+    pub fn due(mut self, end_date: NaiveDate) -> Self {
+        self.task.end_date = Some(end_date);
+        self
+    }
+
+    /// Set how long the Task should take
+    pub fn duration(mut self, duration: u32, unit: DurationUnit) -> Self {
+        self.task.duration = Some(duration);
+        self.task.duration_type = Some(unit);
+        self
+    }
+
+    /// Set the priority of the Task
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.task.priority = Some(priority);
+        self
+    }
+
+    /// Set the description of the Task
+    pub fn description(mut self, description: &str) -> Self {
+        self.task.description = Some(description.to_owned());
+        self
+    }
+
+    /// Set the rate charged per hour spent on the Task
+    pub fn rate_per_hour(mut self, rate_per_hour: f64) -> Self {
+        self.task.rate_per_hour = Some(rate_per_hour);
+        self
+    }
+
+    /// Set the value of one of the Project's custom fields, by its column name, e.g. "UDF_CHAR1".
+    /// May be called once for each field.
+    pub fn custom_field(mut self, column_name: &str, value: &str) -> Self {
+        self.task
+            .custom_fields
+            .get_or_insert_with(BTreeMap::new)
+            .insert(column_name.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Generate the NewTask
+    pub fn build(self) -> NewTask {
+        self.task
+    }
+}
+
+/// How urgent a Task is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Priority {
+    /// No priority set
+    None,
+    /// Can wait until more pressing Tasks are done
+    Low,
+    /// Should be done in the normal course of work
+    Medium,
+    /// Should be done ahead of other Tasks
+    High,
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str_rep = match self {
+            Priority::None => "None",
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+        };
+
+        write!(f, "{}", str_rep)
+    }
+}
+
+/// Units in which the duration of a Task is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DurationUnit {
+    /// A duration given in days
+    #[serde(rename = "days")]
+    Days,
+    /// A duration given in hours
+    #[serde(rename = "hrs")]
+    Hours,
+}
+
+impl std::fmt::Display for DurationUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str_rep = match self {
+            DurationUnit::Days => "days",
+            DurationUnit::Hours => "hrs",
+        };
+
+        write!(f, "{}", str_rep)
+    }
+}
+
+/// Changes to make to an existing Task; only the fields which are set are sent.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct UpdateTask {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// IDs of the users to assign the Task to
+    #[serde(
+        rename = "person_responsible",
+        serialize_with = "join_ids",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub person_responsible: Option<Vec<i64>>,
+    #[serde(
+        rename = "start_date",
+        serialize_with = "zoho_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_date: Option<NaiveDate>,
+    #[serde(
+        rename = "end_date",
+        serialize_with = "zoho_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_date: Option<NaiveDate>,
+    #[serde(rename = "priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(rename = "percent_complete", skip_serializing_if = "Option::is_none")]
+    pub percent_complete: Option<u8>,
+    #[serde(rename = "custom_status", skip_serializing_if = "Option::is_none")]
+    pub custom_status: Option<String>,
+    #[serde(rename = "tasklist_id", skip_serializing_if = "Option::is_none")]
+    pub tasklist_id: Option<i64>,
+    /// Length of the Task, in `duration_type` units
+    #[serde(rename = "duration", skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(rename = "duration_type", skip_serializing_if = "Option::is_none")]
+    pub duration_type: Option<DurationUnit>,
+    #[serde(rename = "rate_per_hour", skip_serializing_if = "Option::is_none")]
+    pub rate_per_hour: Option<f64>,
+    /// Values for the Project's custom fields, keyed by column name, e.g. "UDF_CHAR1"
+    #[serde(
+        rename = "custom_fields",
+        serialize_with = "json_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub custom_fields: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -229,6 +569,9 @@ pub struct Task {
     pub status: Status,
     #[serde(rename = "subtasks")]
     pub subtasks: bool,
+    /// The Project this Task belongs to; only present on Tasks from `my_tasks`
+    #[serde(rename = "project", default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<OwningProject>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -311,120 +654,122 @@ pub struct Tasklist {
     pub name: String,
 }
 
-#[derive(Debug, Clone)]
+/// Iterator across every Task, fetching them from Zoho a page at a time. If the request was
+/// made `with_subtasks`, the subtasks of each Task are fetched once the Tasks run out, and
+/// their subtasks in turn. The first pages of several lists of subtasks are fetched at once,
+/// all drawing on the client's rate limit, but they are always yielded in the order their
+/// parents were. As with Paginator, an error fetching any page is yielded and then iteration
+/// ends.
+#[derive(Debug)]
 pub struct TaskIterator {
-    pub items: <Vec<Task> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: TaskRequest,
-    pub start_index: usize,
-    pub subtask_parent_ids: Vec<i64>,
-    pub base_path: String,
-    pub rate_limit_notified: bool,
+    pages: Paginator<TaskRequest>,
+    // First pages of subtask lists fetched ahead, by parent, until their turn comes.
+    fetched: HashMap<i64, Result<ZohoTasks>>,
+    concurrency: usize,
 }
 
 impl TaskIterator {
     pub fn new(request: TaskRequest) -> TaskIterator {
         TaskIterator {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            start_index: 0,
-            subtask_parent_ids: Vec::new(),
-            base_path: request.details.model_path.clone(),
-            request,
-            rate_limit_notified: false,
+            pages: Paginator::new(request),
+            fetched: HashMap::new(),
+            concurrency: DEFAULT_SUBTASK_CONCURRENCY,
         }
     }
 
-    pub fn try_next(&mut self) -> Result<Option<Task>> {
-        use std::{thread, time};
-
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(task) = self.items.next() {
-            // While we're looking at the task, check if we're fetching subtasks
-            // and should be expecting subtasks for this task. If we do and are,
-            // save the ID of this task for use in the URL later.
-            if task.subtasks && self.request.with_subtasks {
-                self.subtask_parent_ids.push(task.id)
-            }
-            return Ok(Some(task));
-        }
+    /// Fetch this many records per request, up to Zoho's limit of 100
+    pub fn page_size(mut self, page_size: u8) -> Self {
+        self.pages = self.pages.page_size(page_size);
+        self
+    }
 
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out of top level tasks in Zoho; don't request any more.
-        if !self.last_full {
-            // If we're not looking for subtasks, we can stop here.
-            if !self.request.with_subtasks {
-                return Ok(None);
-            }
+    /// Stop after this many Tasks in total, counting subtasks, requesting no more than
+    /// needed to reach it
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.pages = self.pages.max_items(max_items);
+        self
+    }
 
-            let subtask_tasks = self.subtask_parent_ids.len();
+    /// Fetch the subtasks of up to this many Tasks at once. Defaults to 4; 1 fetches them one
+    /// parent at a time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Continue from a Cursor saved earlier, rather than the first Task
+    pub fn resume(mut self, cursor: Cursor) -> Self {
+        self.fetched.clear();
+        self.pages = self.pages.resume(cursor);
+        self
+    }
 
-            if subtask_tasks > 99 && !self.rate_limit_notified {
-                eprintln!(
-                    "{} tasks have subtasks. This would exceed Zoho's rate limit (100 requests per 120 seconds) \
-                    if processed at full speed. Processing will be artificially slowed to that limit.",
-                    subtask_tasks
-                );
-                self.rate_limit_notified = true;
+    /// The position of the next Task to be returned, including any subtask lists still to be
+    /// fetched, to `resume` from later.
+    pub fn cursor(&self) -> Cursor {
+        self.pages.cursor()
+    }
+
+    /// Fetch every remaining Task, failing if any page cannot be fetched
+    pub fn try_collect_all(self) -> Result<Vec<Task>> {
+        self.collect()
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<Task>> {
+        loop {
+            if let Some(task) = self.pages.take_fetched() {
+                return Ok(Some(task));
             }
 
-            // Otherwise, start popping IDs off our queue of subtask parents
-            // and requesting their children.
-            if let Some(id) = self.subtask_parent_ids.pop() {
-                self.start_index = 0;
-                // FIXME(Xymist): This will crash if this is a single task search.
-                // I.e. if an ID was provided to the initial request.
-                self.request.details.model_path = format!("{}{}/subtasks/", self.base_path, id)
-            } else {
-                // We have run out of not only top level tasks, but all subtasks.
+            if let Some(request) = self.pages.next_page() {
+                let page = match self.pages.starting_listing() {
+                    Some(parent_id) => self.fetch_ahead(parent_id, request),
+                    None => request.get(),
+                };
+                // Give up on any subtasks fetched ahead, so that iteration ends after the error.
+                self.pages
+                    .receive(page)
+                    .inspect_err(|_| self.fetched.clear())?;
+            } else if !self.pages.next_listing() {
                 return Ok(None);
             }
         }
+    }
 
-        if self.rate_limit_notified {
-            thread::sleep(time::Duration::from_millis(1250));
+    // The first page of the subtasks of this Task, fetched along with those of the next few
+    // Tasks in the queue, unless that was done already.
+    fn fetch_ahead(&mut self, parent_id: i64, request: TaskRequest) -> Result<ZohoTasks> {
+        if let Some(page) = self.fetched.remove(&parent_id) {
+            return page;
         }
 
-        let returned_tasks = self
-            .request
-            .clone()
-            .filter(Filter::Index(self.start_index))
-            .get();
-
-        match returned_tasks {
-            Ok(Some(task_list)) => {
-                if task_list.tasks.is_empty() {
-                    self.last_full = false;
-                    return Ok(None);
-                }
+        let mut requests = vec![(parent_id, request)];
+        requests.extend(
+            self.pages
+                .pending()
+                .take(self.concurrency - 1)
+                .filter_map(|id| Some((id, self.pages.first_page_of(id)?))),
+        );
 
-                self.last_full = task_list.tasks.len() as i8 == self.range();
+        let mut pages: Vec<(i64, Result<ZohoTasks>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = requests
+                .into_iter()
+                .map(|(id, request)| (id, scope.spawn(move || request.get())))
+                .collect();
+            handles
+                .into_iter()
+                .map(|(id, handle)| {
+                    let page = handle
+                        .join()
+                        .unwrap_or_else(|_| Err(Error::transport("subtask fetch panicked")));
+                    (id, page)
+                })
+                .collect()
+        });
 
-                self.start_index += task_list.tasks.len();
-
-                self.items = task_list.tasks.into_iter();
-
-                Ok(self.items.next())
-            }
-            Ok(None) => {
-                self.last_full = false;
-                Ok(None)
-            }
-            Err(err) => {
-                self.last_full = false;
-                Err(dbg!(err))
-            }
-        }
-    }
-
-    fn range(&self) -> i8 {
-        match self.request.params() {
-            Some(params) => match params.get("range") {
-                Some(range_string) => range_string.parse::<i8>().unwrap_or(100),
-                None => 100,
-            },
-            None => 100,
-        }
+        let (_, page) = pages.remove(0);
+        self.fetched.extend(pages);
+        page
     }
 }
 
@@ -435,10 +780,304 @@ impl Iterator for TaskIterator {
         match self.try_next() {
             Ok(Some(val)) => Some(Ok(val)),
             Ok(None) => None,
-            Err(err) => {
-                warn!("Fetching Tasks from Zoho experienced an error: {}", err);
-                None
-            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::task_json;
+    use crate::transport::{HttpRequest, HttpResponse, MockTransport, Transport};
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    // Answers each request by its path, so that the order subtasks are fetched in, and the
+    // order those fetches finish in, can differ from the order the answers are expected in.
+    #[derive(Debug)]
+    struct Routes(Vec<Route>);
+
+    // The end of a path, how long to take answering it, and the ID of each Task listed there
+    // with whether it has subtasks.
+    type Route = (&'static str, Duration, Vec<(i64, bool)>);
+
+    impl Transport for Routes {
+        fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+            let route = self.0.iter().find(|(path, ..)| request.url.ends_with(path));
+            let (_, delay, tasks) = match route {
+                Some(route) => route,
+                None => return Ok(HttpResponse::new(StatusCode::NOT_FOUND, "")),
+            };
+            std::thread::sleep(*delay);
+
+            let index = request
+                .query_param("index")
+                .and_then(|index| index.parse().ok())
+                .unwrap_or(0);
+            let tasks: Vec<serde_json::Value> = tasks
+                .iter()
+                .skip(index)
+                .map(|(id, subtasks)| task_json(*id, *subtasks))
+                .collect();
+            let body = serde_json::json!({ "tasks": tasks }).to_string();
+            Ok(HttpResponse::new(StatusCode::OK, body))
+        }
+    }
+
+    // Tasks 1 and 2 have subtasks, and subtask 11 has its own; the subtasks of 1 arrive last.
+    fn tasks() -> TaskRequest {
+        let routes = Routes(vec![
+            (
+                "/projects/2/tasks/",
+                Duration::from_millis(0),
+                vec![(1, true), (2, true), (3, false)],
+            ),
+            (
+                "/tasks/1/subtasks/",
+                Duration::from_millis(100),
+                vec![(11, true)],
+            ),
+            (
+                "/tasks/2/subtasks/",
+                Duration::from_millis(0),
+                vec![(21, false), (22, false)],
+            ),
+            (
+                "/tasks/11/subtasks/",
+                Duration::from_millis(0),
+                vec![(111, false)],
+            ),
+        ]);
+        let client = ZohoClient::with_access_token("token", routes);
+        TaskRequest::new(&client, &model_path(1, 2), None).with_subtasks()
+    }
+
+    fn ids(tasks: TaskIterator) -> Vec<i64> {
+        tasks
+            .try_collect_all()
+            .expect("every page fetched")
+            .iter()
+            .map(|task| task.id)
+            .collect()
+    }
+
+    #[test]
+    fn subtasks_follow_in_the_order_of_their_parents() {
+        assert_eq!(
+            ids(tasks().iter_get().concurrency(4)),
+            vec![1, 2, 3, 11, 21, 22, 111]
+        );
+        assert_eq!(
+            ids(tasks().iter_get().concurrency(1)),
+            vec![1, 2, 3, 11, 21, 22, 111]
+        );
+    }
+
+    #[test]
+    fn subtasks_are_only_fetched_on_request() {
+        let request = tasks();
+        let request = TaskRequest {
+            with_subtasks: false,
+            ..request
+        };
+        assert_eq!(ids(request.iter_get()), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn max_items_counts_subtasks() {
+        assert_eq!(ids(tasks().iter_get().max_items(5)), vec![1, 2, 3, 11, 21]);
+    }
+
+    #[test]
+    fn resume_within_subtasks() {
+        let mut iterator = tasks().iter_get();
+        for _ in 0..5 {
+            iterator.try_next().unwrap();
         }
+        let cursor = iterator.cursor();
+        assert_eq!(cursor.parent_id, Some(2));
+        assert_eq!(cursor.start_index, 1);
+        assert_eq!(cursor.subtask_parent_ids, vec![11]);
+
+        assert_eq!(ids(tasks().resume(cursor)), vec![22, 111]);
+    }
+
+    #[test]
+    fn dates_are_sent_month_first() {
+        let task = NewTask::builder("Launch")
+            .start(NaiveDate::from_ymd_opt(2024, 3, 9).unwrap())
+            .due(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap())
+            .build();
+        let encoded = serde_urlencoded::to_string(&task).unwrap();
+        assert!(encoded.contains("start_date=03-09-2024"), "{}", encoded);
+        assert!(encoded.contains("end_date=12-25-2024"), "{}", encoded);
+
+        let update = UpdateTask {
+            end_date: NaiveDate::from_ymd_opt(2025, 1, 2),
+            ..UpdateTask::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(&update).unwrap(),
+            "end_date=01-02-2025"
+        );
+    }
+
+    #[test]
+    fn custom_fields_are_sent_as_json() {
+        let task = NewTask::builder("Launch")
+            .custom_field("UDF_CHAR1", "blue")
+            .build();
+        let encoded = serde_urlencoded::to_string(&task).unwrap();
+        assert!(
+            encoded.contains("custom_fields=%7B%22UDF_CHAR1%22%3A%22blue%22%7D"),
+            "{}",
+            encoded
+        );
+    }
+
+    #[test]
+    fn priority_filter_sends_its_name() {
+        let transport = MockTransport::new();
+        let request = project_tasks(&transport, None).filter(Filter::Priority(Priority::High));
+        let params = request.params().unwrap();
+        assert_eq!(params.get("priority").map(String::as_str), Some("High"));
+    }
+
+    #[test]
+    fn subtasks_of_a_single_task_are_listed() {
+        let transport = MockTransport::new();
+        let task = serde_json::json!({ "tasks": [task_json(5, true)] }).to_string();
+        transport.respond(StatusCode::OK, &task);
+        let subtasks = serde_json::json!({ "tasks": [task_json(51, false)] }).to_string();
+        transport.respond(StatusCode::OK, &subtasks);
+
+        let found = project_tasks(&transport, Some(5))
+            .with_subtasks()
+            .iter_get()
+            .try_collect_all()
+            .unwrap();
+        assert_eq!(
+            found.iter().map(|task| task.id).collect::<Vec<_>>(),
+            [5, 51]
+        );
+
+        let requests = transport.requests();
+        assert!(requests[0].url.ends_with("/projects/2/tasks/5/"));
+        assert!(requests[1].url.ends_with("/projects/2/tasks/5/subtasks/"));
+    }
+
+    // A single Task, returned as Zoho does for a creation or update.
+    fn task_response(transport: &MockTransport, id: i64) {
+        let body = serde_json::json!({ "tasks": [task_json(id, false)] }).to_string();
+        transport.respond(StatusCode::OK, &body);
+    }
+
+    fn project_tasks(transport: &MockTransport, id: Option<i64>) -> TaskRequest {
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        TaskRequest::new(&client, &model_path(1, 2), id)
+    }
+
+    #[test]
+    fn create_posts_to_the_collection() {
+        let transport = MockTransport::new();
+        task_response(&transport, 5);
+
+        let task = project_tasks(&transport, None)
+            .post(NewTask::builder("Launch").build())
+            .unwrap();
+        assert_eq!(task.id, 5);
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::POST);
+        assert!(request.url.ends_with("/portal/1/projects/2/tasks/"));
+        assert_eq!(
+            request.body.as_ref().and_then(|body| body.field("name")),
+            Some("Launch")
+        );
+    }
+
+    #[test]
+    fn create_with_an_id_is_refused() {
+        let transport = MockTransport::new();
+        let result = project_tasks(&transport, Some(5)).post(NewTask::builder("Launch").build());
+        assert!(matches!(result, Err(Error::UnexpectedRecordId { .. })));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn update_posts_to_the_record() {
+        let transport = MockTransport::new();
+        task_response(&transport, 5);
+
+        let update = UpdateTask {
+            name: Some("Relaunch".to_owned()),
+            ..UpdateTask::default()
+        };
+        let task = project_tasks(&transport, Some(5)).put(update).unwrap();
+        assert_eq!(task.id, 5);
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::POST);
+        assert!(request.url.ends_with("/portal/1/projects/2/tasks/5/"));
+        let body = request.body.as_ref().expect("a form body");
+        assert_eq!(body.field("name"), Some("Relaunch"));
+        assert_eq!(body.field("description"), None);
+    }
+
+    #[test]
+    fn update_and_delete_without_an_id_are_refused() {
+        let transport = MockTransport::new();
+        let tasks = project_tasks(&transport, None);
+        assert!(matches!(
+            tasks.put(UpdateTask::default()),
+            Err(Error::MissingRecordId { .. })
+        ));
+        assert!(matches!(tasks.delete(), Err(Error::MissingRecordId { .. })));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn get_one_reads_the_record() {
+        let transport = MockTransport::new();
+        task_response(&transport, 5);
+
+        let task = project_tasks(&transport, Some(5)).get_one().unwrap();
+        assert_eq!(task.id, 5);
+        assert!(transport.requests()[0]
+            .url
+            .ends_with("/portal/1/projects/2/tasks/5/"));
+    }
+
+    #[test]
+    fn get_one_without_a_record_is_not_found() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::NO_CONTENT, "");
+
+        let result = project_tasks(&transport, Some(5)).get_one();
+        assert!(matches!(result, Err(Error::NotFound(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn get_one_without_an_id_is_refused() {
+        let transport = MockTransport::new();
+        let result = project_tasks(&transport, None).get_one();
+        assert!(matches!(result, Err(Error::MissingRecordId { .. })));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn delete_returns_zoho_confirmation() {
+        let transport = MockTransport::new();
+        transport.respond(
+            StatusCode::OK,
+            r#"{"response": "Task deleted successfully"}"#,
+        );
+
+        let deleted = project_tasks(&transport, Some(5)).delete().unwrap();
+        assert_eq!(deleted.response, "Task deleted successfully");
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::DELETE);
+        assert!(request.url.ends_with("/portal/1/projects/2/tasks/5/"));
     }
 }
@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
-use tracing::warn;
 
-use crate::errors::*;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use crate::serializers::from_str;
 use std::collections::HashMap;
 
@@ -19,12 +19,12 @@ pub(crate) fn model_path(
 pub struct TasklistRequest(RequestDetails);
 
 impl TasklistRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        TasklistRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        TasklistRequest(RequestDetails::new(client, model_path, id))
     }
 
     pub fn iter_get(self) -> TasklistIterator {
-        TasklistIterator::new(self)
+        Paginator::new(self)
     }
 }
 
@@ -33,15 +33,19 @@ impl ModelRequest for TasklistRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -50,6 +54,7 @@ impl ModelRequest for TasklistRequest {
 impl RequestParameters for TasklistRequest {
     type ModelCollection = ZohoTasklists;
     type NewModel = NewTasklist;
+    type UpdateModel = UpdateTasklist;
 }
 
 pub enum Filter {
@@ -108,6 +113,14 @@ pub struct ZohoTasklists {
     pub tasklists: Vec<Tasklist>,
 }
 
+impl Collection for ZohoTasklists {
+    type Model = Tasklist;
+
+    fn into_models(self) -> Vec<Tasklist> {
+        self.tasklists
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NewTasklist {
     name: String,
@@ -115,6 +128,20 @@ pub struct NewTasklist {
     flag: Flag,
 }
 
+/// Changes to make to an existing Tasklist; only the fields which are set are sent.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateTasklist {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<Flag>,
+    /// "active" or "completed"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
 // TODO(Xymist): Implement Tasklist::tasks() to create a new request to fetch all tasks for a tasklist
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Tasklist {
@@ -188,85 +215,5 @@ pub struct MilestoneLink {
     pub status: Link,
 }
 
-#[derive(Clone, Debug)]
-pub struct TasklistIterator {
-    pub items: <Vec<Tasklist> as IntoIterator>::IntoIter,
-    pub last_full: bool,
-    pub request: TasklistRequest,
-    pub start_index: usize,
-}
-
-impl TasklistIterator {
-    pub fn new(request: TasklistRequest) -> TasklistIterator {
-        TasklistIterator {
-            items: Vec::new().into_iter(),
-            last_full: true,
-            request,
-            start_index: 0,
-        }
-    }
-
-    fn range(&self) -> i8 {
-        match self.request.params() {
-            Some(params) => match params.get("range") {
-                Some(range_string) => range_string.parse::<i8>().unwrap_or(100),
-                None => 100,
-            },
-            None => 100,
-        }
-    }
-
-    pub fn try_next(&mut self) -> Result<Option<Tasklist>> {
-        // If there are still items in the local cache from the last request, use the next one of those.
-        if let Some(tasklist) = self.items.next() {
-            return Ok(Some(tasklist));
-        }
-
-        // If we didn't get a full 100 (the default number to retrieve) the last time, then we must have
-        // run out in Zoho; don't request any more.
-        if !self.last_full {
-            return Ok(None);
-        }
-
-        let returned_tasklists = self
-            .request
-            .clone()
-            .filter(Filter::Index(self.start_index))
-            .get();
-
-        match returned_tasklists {
-            Ok(Some(tasklist_list)) => {
-                self.last_full = tasklist_list.tasklists.len() as i8 == self.range();
-
-                self.start_index += tasklist_list.tasklists.len();
-
-                self.items = tasklist_list.tasklists.into_iter();
-
-                Ok(self.items.next())
-            }
-            Ok(None) => {
-                self.last_full = false;
-                Ok(None)
-            }
-            Err(err) => {
-                self.last_full = false;
-                Err(err)
-            }
-        }
-    }
-}
-
-impl Iterator for TasklistIterator {
-    type Item = Result<Tasklist>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.try_next() {
-            Ok(Some(val)) => Some(Ok(val)),
-            Ok(None) => None,
-            Err(err) => {
-                warn!("Fetching Tasklists from Zoho experienced an error: {}", err);
-                None
-            }
-        }
-    }
-}
+/// Iterator across every Tasklist, fetching them from Zoho a page at a time
+pub type TasklistIterator = Paginator<TasklistRequest>;
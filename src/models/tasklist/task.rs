@@ -1,3 +1,5 @@
+use crate::client::ZohoClient;
+use crate::paginator::Paginator;
 use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use std::collections::HashMap;
 
@@ -16,8 +18,14 @@ pub(crate) fn model_path(
 pub struct TasklistTaskRequest(RequestDetails);
 
 impl TasklistTaskRequest {
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        TasklistTaskRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        TasklistTaskRequest(RequestDetails::new(client, model_path, id))
+    }
+
+    /// Return a Paginator, which allows iteration across every record in turn,
+    /// fetching them from Zoho a page at a time
+    pub fn iter_get(self) -> Paginator<Self> {
+        Paginator::new(self)
     }
 }
 
@@ -26,15 +34,19 @@ impl ModelRequest for TasklistTaskRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -43,4 +55,5 @@ impl ModelRequest for TasklistTaskRequest {
 impl RequestParameters for TasklistTaskRequest {
     type ModelCollection = crate::models::task::ZohoTasks;
     type NewModel = crate::models::task::NewTask;
+    type UpdateModel = crate::models::task::UpdateTask;
 }
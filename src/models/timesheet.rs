@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::client::ZohoClient;
 use crate::models::multi_filter_format;
-use crate::request::{FilterOptions, ModelRequest, RequestDetails, RequestParameters};
+use crate::request::{Collection, FilterOptions, ModelRequest, RequestDetails, RequestParameters};
 use std::collections::HashMap;
 
 pub(crate) fn model_path(
@@ -16,8 +17,8 @@ pub struct TimesheetRequest(RequestDetails);
 
 impl TimesheetRequest {
     /// Construct a new TimesheetRequest
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
-        TimesheetRequest(RequestDetails::new(access_token, model_path, id))
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
+        TimesheetRequest(RequestDetails::new(client, model_path, id))
     }
 }
 
@@ -26,15 +27,19 @@ impl ModelRequest for TimesheetRequest {
         self.0.uri()
     }
 
+    fn id(&self) -> Option<i64> {
+        self.0.id()
+    }
+
     fn params(&self) -> Option<HashMap<String, String>> {
         self.0.params()
     }
 
-    fn access_token(&self) -> String {
-        self.0.access_token()
+    fn client(&self) -> &ZohoClient {
+        self.0.client()
     }
 
-    fn filter(mut self, param: (impl FilterOptions + std::fmt::Display)) -> Self {
+    fn filter(mut self, param: impl FilterOptions + std::fmt::Display) -> Self {
         self.0 = self.0.filter(&param);
         self
     }
@@ -43,6 +48,7 @@ impl ModelRequest for TimesheetRequest {
 impl RequestParameters for TimesheetRequest {
     type ModelCollection = ZohoTimeLogs;
     type NewModel = NewTimeLog;
+    type UpdateModel = UpdateTimeLog;
 }
 
 pub enum Filter {
@@ -145,12 +151,23 @@ impl std::fmt::Display for ComponentType {
     }
 }
 
+// Zoho groups logs by day, while its `index` and `range` parameters count individual logs, so
+// these can't be paged through a day at a time; there is no iter_get until logs are modelled
+// singly.
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ZohoTimeLogs {
     #[serde(rename = "timelogs")]
     pub timelogs: TimeLogs,
 }
 
+impl Collection for ZohoTimeLogs {
+    type Model = DateLog;
+
+    fn into_models(self) -> Vec<DateLog> {
+        self.timelogs.timelog.date
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct TimeLogs {
     #[serde(rename = "grandtotal")]
@@ -170,6 +187,9 @@ pub struct TimeLog {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct NewTimeLog {}
 
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct UpdateTimeLog {}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct DateLog {
     #[serde(rename = "date_long")]
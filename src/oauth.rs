@@ -1,37 +1,204 @@
 use oauth2::basic::BasicClient;
 use oauth2::{
-    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, RedirectUrl, Scope, TokenUrl,
+    AuthUrl, AuthorizationCode, ClientId, CsrfToken, PkceCodeChallenge, PkceCodeVerifier,
+    RedirectUrl, Scope,
 };
+use reqwest::Method;
 use serde::Deserialize;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use std::collections::HashMap;
+use crate::errors::*;
+use crate::retry::RetryPolicy;
+use crate::token_store::{StoredTokens, TokenStore};
+use crate::transport::{HttpRequest, RequestBody, Transport};
+
+use std::borrow::Cow;
 use std::io::{BufRead, BufReader, Write};
 use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
 use std::time::{Duration, SystemTime};
 use url::Url;
 
-#[derive(Debug, Clone)]
+// Treat access tokens as expired slightly early, so that one doesn't lapse between being
+// handed out and the request carrying it reaching Zoho.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// How a ZohoClient obtains its first access token, when there is no refresh token to use.
+#[derive(Clone, Default)]
+pub enum AuthMode {
+    /// Open the authorization page in a browser and catch the redirect with a listener on
+    /// localhost. Requires a desktop session.
+    #[default]
+    Browser,
+    /// Exchange a grant code generated for a "Self Client" in the Zoho API console. These are
+    /// single-use and short-lived, so the refresh token obtained with it should be kept in a
+    /// TokenStore.
+    SelfClient(String),
+    /// Print the authorization URL and read from stdin either the URL the browser was
+    /// redirected to or the bare code from it.
+    Manual,
+    /// Pass the authorization URL to the callback, which returns either the URL the browser
+    /// was redirected to or the bare code from it.
+    Callback(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl std::fmt::Debug for AuthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthMode::Browser => write!(f, "Browser"),
+            AuthMode::SelfClient(_) => write!(f, "SelfClient(..)"),
+            AuthMode::Manual => write!(f, "Manual"),
+            AuthMode::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+/// The set of OAuth scopes to request, which bounds what the resulting token may do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scopes(Vec<String>);
+
+impl Scopes {
+    /// Every scope this crate makes use of, including creating and deleting records
+    pub fn full() -> Self {
+        Scopes::new(&[
+            "ZohoProjects.portals.READ",
+            "ZohoProjects.projects.ALL",
+            "ZohoProjects.events.ALL",
+            "ZohoProjects.bugs.ALL",
+            "ZohoProjects.status.READ",
+            "ZohoProjects.status.CREATE",
+            "ZohoProjects.forums.ALL",
+            "ZohoProjects.milestones.ALL",
+            "ZohoProjects.tasks.ALL",
+            "ZohoProjects.tasklists.ALL",
+            "ZohoProjects.users.ALL",
+            "ZohoProjects.documents.READ",
+            "ZohoProjects.documents.CREATE",
+            "ZohoProjects.documents.UPDATE",
+            "ZohoProjects.documents.DELETE",
+            "ZohoPC.files.READ",
+            "ZohoPC.files.CREATE",
+            "ZohoPC.files.DELETE",
+        ])
+    }
+
+    /// Read access to everything this crate can retrieve, and nothing more
+    pub fn read_only() -> Self {
+        Scopes::new(&[
+            "ZohoProjects.portals.READ",
+            "ZohoProjects.projects.READ",
+            "ZohoProjects.events.READ",
+            "ZohoProjects.bugs.READ",
+            "ZohoProjects.status.READ",
+            "ZohoProjects.forums.READ",
+            "ZohoProjects.milestones.READ",
+            "ZohoProjects.tasks.READ",
+            "ZohoProjects.tasklists.READ",
+            "ZohoProjects.users.READ",
+            "ZohoProjects.documents.READ",
+            "ZohoPC.files.READ",
+        ])
+    }
+
+    /// A custom set of scopes, e.g. `&["ZohoProjects.portals.READ", "ZohoProjects.tasks.ALL"]`
+    pub fn new(scopes: &[&str]) -> Self {
+        Scopes(scopes.iter().map(|scope| (*scope).to_owned()).collect())
+    }
+
+    /// Add a further scope to this set
+    pub fn with(mut self, scope: &str) -> Self {
+        if !self.0.iter().any(|s| s == scope) {
+            self.0.push(scope.to_owned());
+        }
+        self
+    }
+
+    /// Iterate over the scopes in this set
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl Default for Scopes {
+    fn default() -> Self {
+        Scopes::full()
+    }
+}
+
+/// The tokens invalidated by a successful revocation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RevokedTokens {
+    /// Whether a refresh token was held and revoked. Zoho also invalidates every access
+    /// token issued from it.
+    pub refresh_token: bool,
+    /// Whether an access token was held and revoked
+    pub access_token: bool,
+}
+
+#[derive(Clone)]
 pub struct Credentials {
     client_id: String,
-    client_secret: String,
+    client_secret: Option<String>,
+    pkce: bool,
     auth_url: String,
     token_url: String,
     access_token: Option<String>,
     access_token_expiry: Option<SystemTime>,
     refresh_token: Option<String>,
+    api_domain: Option<String>,
+    store: Option<Arc<dyn TokenStore>>,
+    auth_mode: AuthMode,
+    scopes: Scopes,
+    redirect_uri: String,
+    listen_addr: Option<String>,
+}
+
+// Secrets are shown only as present or absent, as a ZohoClient is often printed whole.
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &redacted(&self.client_secret))
+            .field("pkce", &self.pkce)
+            .field("auth_url", &self.auth_url)
+            .field("token_url", &self.token_url)
+            .field("access_token", &redacted(&self.access_token))
+            .field("access_token_expiry", &self.access_token_expiry)
+            .field("refresh_token", &redacted(&self.refresh_token))
+            .field("api_domain", &self.api_domain)
+            .field("store", &redacted(&self.store))
+            .field("auth_mode", &self.auth_mode)
+            .field("scopes", &self.scopes)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("listen_addr", &self.listen_addr)
+            .finish()
+    }
+}
+
+struct Redacted;
+
+impl std::fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "..")
+    }
+}
+
+fn redacted<T>(secret: &Option<T>) -> Option<Redacted> {
+    secret.as_ref().map(|_| Redacted)
 }
 
 impl Credentials {
     pub fn new(
         client_id: &str,
-        client_secret: &str,
+        client_secret: Option<&str>,
         auth_url: Option<&str>,
         token_url: Option<&str>,
     ) -> Self {
         Credentials {
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
+            client_secret: client_secret.map(str::to_owned),
+            pkce: false,
             auth_url: auth_url
                 .unwrap_or("https://accounts.zoho.com/oauth/v2/auth")
                 .to_owned(),
@@ -41,37 +208,280 @@ impl Credentials {
             access_token: None,
             access_token_expiry: None,
             refresh_token: None,
+            api_domain: None,
+            store: None,
+            auth_mode: AuthMode::default(),
+            scopes: Scopes::default(),
+            redirect_uri: "http://localhost:8080/".to_owned(),
+            listen_addr: None,
+        }
+    }
+
+    /// Credentials wrapping an access token obtained elsewhere. Having no known expiry or
+    /// refresh token, this will be used as-is until Zoho rejects it.
+    pub fn with_access_token(access_token: &str) -> Self {
+        Credentials {
+            access_token: Some(access_token.to_owned()),
+            ..Credentials::new("", None, None, None)
+        }
+    }
+
+    /// Point authorization and token requests at the given accounts server, e.g.
+    /// `https://accounts.zoho.eu`.
+    pub fn set_accounts_url(&mut self, accounts_url: &str) {
+        self.auth_url = format!("{}/oauth/v2/auth", accounts_url);
+        self.token_url = format!("{}/oauth/v2/token", accounts_url);
+    }
+
+    /// Choose how the initial access token is obtained
+    pub fn set_auth_mode(&mut self, auth_mode: AuthMode) {
+        self.auth_mode = auth_mode;
+    }
+
+    /// Send a PKCE challenge with authorization requests and the matching verifier when
+    /// exchanging the code. Public clients, which have no secret, always use PKCE.
+    pub fn set_pkce(&mut self, pkce: bool) {
+        self.pkce = pkce;
+    }
+
+    fn uses_pkce(&self) -> bool {
+        self.pkce || self.client_secret.is_none()
+    }
+
+    /// Choose which scopes to request when authorizing
+    pub fn set_scopes(&mut self, scopes: Scopes) {
+        self.scopes = scopes;
+    }
+
+    /// Set the URI Zoho redirects to after authorization. This must match one registered for
+    /// the client in the Zoho API console.
+    pub fn set_redirect_uri(&mut self, redirect_uri: &str) {
+        self.redirect_uri = redirect_uri.to_owned();
+    }
+
+    /// Set the address the browser flow listens on for the redirect. By default this is
+    /// 127.0.0.1 on the port of the redirect URI.
+    pub fn set_listen_addr(&mut self, listen_addr: &str) {
+        self.listen_addr = Some(listen_addr.to_owned());
+    }
+
+    fn listen_addr(&self) -> Result<String> {
+        if let Some(ref addr) = self.listen_addr {
+            return Ok(addr.clone());
+        }
+
+        let redirect = Url::parse(&self.redirect_uri)
+            .map_err(|e| Error::oauth(OAuthErrorKind::InvalidConfiguration, &e.to_string()))?;
+        let port = redirect.port_or_known_default().ok_or_else(|| {
+            Error::oauth(OAuthErrorKind::InvalidConfiguration, &self.redirect_uri)
+        })?;
+        Ok(format!("127.0.0.1:{}", port))
+    }
+
+    /// Attach a TokenStore to these Credentials, adopting any tokens already saved in it.
+    /// Every token issued from here on is saved back to the store.
+    pub fn set_store(&mut self, store: Arc<dyn TokenStore>) -> Result<()> {
+        let stored = store.load();
+        self.store = Some(store);
+
+        if let Some(tokens) = stored? {
+            self.access_token = tokens.access_token;
+            self.access_token_expiry = tokens.expires_at.map(SystemTime::from);
+            self.refresh_token = tokens.refresh_token;
+            self.api_domain = tokens.api_domain;
+        }
+
+        Ok(())
+    }
+
+    // Write the current tokens to the attached store, if there is one. A failure here doesn't
+    // invalidate the tokens just issued, so it's reported rather than returned.
+    fn persist(&self) {
+        if let Some(ref store) = self.store {
+            let tokens = StoredTokens {
+                access_token: self.access_token.clone(),
+                expires_at: self.access_token_expiry.map(Into::into),
+                refresh_token: self.refresh_token.clone(),
+                api_domain: self.api_domain.clone(),
+            };
+
+            if let Err(err) = store.save(&tokens) {
+                warn!("Failed to save Zoho tokens to store: {}", err);
+            }
         }
     }
 
-    pub fn access_token(&mut self) -> String {
+    /// Returns a current access token, renewing it first if it is missing or has expired.
+    /// Where a refresh token is held that is used to renew silently; the interactive browser
+    /// flow is only run when there is no refresh token or Zoho refuses it. Failing to reach
+    /// Zoho at all is returned as an error, rather than waiting on a browser in its place.
+    pub fn access_token(
+        &mut self,
+        transport: &dyn Transport,
+        retry_policy: &RetryPolicy,
+    ) -> Result<String> {
         let missing = self.access_token.is_none();
 
         if missing || self.outdated() {
-            let mut oauth_client = ZohoClient::new(self.clone());
+            let refreshed = match self.refresh_token {
+                Some(_) => {
+                    let now = SystemTime::now();
+                    let token_response = self.request_refresh(transport, retry_policy)?;
+                    match token_response.error {
+                        Some(ref error) => {
+                            warn!(
+                                "Zoho refused the refresh token ({}), falling back to authorization",
+                                error
+                            );
+                            false
+                        }
+                        None => {
+                            self.update(token_response, now)?;
+                            true
+                        }
+                    }
+                }
+                None => false,
+            };
 
-            oauth_client.request_access();
-            let creds = oauth_client.credentials();
+            if !refreshed {
+                let mut oauth_client = ZohoClient::new(self.clone(), transport, *retry_policy)?;
 
-            self.access_token = creds.raw_access_token();
-            self.access_token_expiry = creds.raw_expiry();
+                match self.auth_mode {
+                    AuthMode::Browser => oauth_client.request_access()?,
+                    AuthMode::SelfClient(ref grant_code) => {
+                        oauth_client.exchange_grant_code(grant_code)?
+                    }
+                    AuthMode::Manual => oauth_client.request_access_manually(read_stdin)?,
+                    AuthMode::Callback(ref callback) => {
+                        oauth_client.request_access_manually(|url| callback(url))?
+                    }
+                }
+                *self = oauth_client.credentials();
+            }
+
+            self.persist();
         }
 
-        self.access_token.clone().unwrap()
+        self.access_token
+            .clone()
+            .ok_or_else(|| Error::oauth(OAuthErrorKind::TokenRequest, "no access token was issued"))
     }
 
-    pub fn raw_access_token(&self) -> Option<String> {
-        self.access_token.clone()
+    /// Returns the access token if one is held and has not expired, without renewing it.
+    #[cfg(feature = "async")]
+    pub fn current_access_token(&self) -> Option<String> {
+        self.access_token.clone().filter(|_| !self.outdated())
     }
 
-    fn raw_expiry(&self) -> Option<SystemTime> {
-        self.access_token_expiry
+    // Ask Zoho for a new access token in exchange for the refresh token, using the
+    // refresh_token grant. A refusal comes back as a response carrying `error`, left for the
+    // caller to act on.
+    fn request_refresh(
+        &self,
+        transport: &dyn Transport,
+        retry_policy: &RetryPolicy,
+    ) -> Result<ZohoTokenResponse> {
+        let refresh_token = match self.refresh_token {
+            Some(ref token) => token.clone(),
+            None => {
+                return Err(Error::oauth(
+                    OAuthErrorKind::MissingRefreshToken,
+                    "no refresh token held",
+                ))
+            }
+        };
+
+        let mut params = vec![
+            ("refresh_token".to_owned(), refresh_token),
+            ("client_id".to_owned(), self.client_id.clone()),
+        ];
+        if let Some(ref client_secret) = self.client_secret {
+            params.push(("client_secret".to_owned(), client_secret.clone()));
+        }
+        params.push(("grant_type".to_owned(), "refresh_token".to_owned()));
+
+        request_token(transport, retry_policy, &self.token_url, params)
+    }
+
+    // Store the details of a successful token response. Zoho only sends a refresh token with the
+    // initial authorization, so an existing one is kept when the response doesn't carry one.
+    fn update(&mut self, token_response: ZohoTokenResponse, issued_at: SystemTime) -> Result<()> {
+        let expires_in = Duration::from_secs(token_response.expires_in_secs());
+        let access_token = match token_response.access_token {
+            Some(token) => token,
+            None => {
+                return Err(Error::oauth(
+                    OAuthErrorKind::TokenRequest,
+                    &token_response
+                        .error
+                        .unwrap_or_else(|| "no access token returned".to_owned()),
+                ))
+            }
+        };
+
+        self.access_token = Some(access_token);
+        self.access_token_expiry = Some(issued_at + expires_in);
+        if token_response.refresh_token.is_some() {
+            self.refresh_token = token_response.refresh_token;
+        }
+        if token_response.api_domain.is_some() {
+            self.api_domain = token_response.api_domain;
+        }
+
+        Ok(())
+    }
+
+    /// Revoke the refresh and access tokens held with Zoho, then forget them and clear the
+    /// attached store. Each token is forgotten, and the store updated, as soon as Zoho has
+    /// revoked it, so a refusal part-way leaves only the tokens still valid, to be retried.
+    /// Revoking the refresh token invalidates its access tokens too, so failing to revoke the
+    /// access token after that is only logged. Once revoked, the next request runs the
+    /// authorization flow afresh.
+    pub fn revoke(&mut self, transport: &dyn Transport) -> Result<RevokedTokens> {
+        let revoke_url = format!("{}/revoke", self.token_url);
+        let mut revoked = RevokedTokens::default();
+
+        if let Some(refresh_token) = self.refresh_token.clone() {
+            revoke_token(transport, &revoke_url, &refresh_token)?;
+            revoked.refresh_token = true;
+            self.refresh_token = None;
+            self.persist();
+        }
+        // An access token will already have lapsed if it has passed its expiry.
+        if let Some(access_token) = self.access_token.clone().filter(|_| !self.outdated()) {
+            match revoke_token(transport, &revoke_url, &access_token) {
+                Ok(()) => revoked.access_token = true,
+                Err(err) if revoked.refresh_token => warn!(
+                    "Failed to revoke Zoho access token, already invalidated with its refresh token: {}",
+                    err
+                ),
+                Err(err) => return Err(err),
+            }
+        }
+
+        self.access_token = None;
+        self.access_token_expiry = None;
+        if let Some(ref store) = self.store {
+            store.clear()?;
+        }
+
+        Ok(revoked)
+    }
+
+    /// The API domain Zoho reported for this account when the token was last issued
+    pub fn api_domain(&self) -> Option<String> {
+        self.api_domain.clone()
     }
 
     fn outdated(&self) -> bool {
         match self.access_token_expiry {
-            Some(expiry) => expiry.duration_since(SystemTime::now()).is_err(),
-            None => true,
+            Some(expiry) => expiry
+                .duration_since(SystemTime::now())
+                .map_or(true, |remaining| remaining < EXPIRY_MARGIN),
+            // Tokens from the authorization flow always have an expiry; one without was supplied
+            // directly and there's no way to tell it has lapsed.
+            None => false,
         }
     }
 }
@@ -81,59 +491,91 @@ impl Credentials {
 struct ZohoTokenResponse {
     access_token: Option<String>,
     refresh_token: Option<String>,
-    expires_in_sec: usize,
-    api_domain: String,
-    token_type: String,
-    expires_in: usize,
+    expires_in_sec: Option<u64>,
+    api_domain: Option<String>,
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: u64,
+    error: Option<String>,
+}
+
+impl ZohoTokenResponse {
+    // Older responses carry expires_in in milliseconds alongside expires_in_sec; newer ones
+    // (and all refresh responses) only send expires_in, in seconds.
+    fn expires_in_secs(&self) -> u64 {
+        self.expires_in_sec.unwrap_or(self.expires_in)
+    }
 }
 
-struct ZohoClient {
+struct ZohoClient<'a> {
     oauth_client: BasicClient,
     credentials: Credentials,
+    transport: &'a dyn Transport,
+    retry_policy: RetryPolicy,
 }
 
-impl ZohoClient {
-    pub(in crate::oauth) fn new(credentials: Credentials) -> Self {
+impl<'a> ZohoClient<'a> {
+    pub(in crate::oauth) fn new(
+        credentials: Credentials,
+        transport: &'a dyn Transport,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
+        let invalid =
+            |e: url::ParseError| Error::oauth(OAuthErrorKind::InvalidConfiguration, &e.to_string());
+
+        // Only used to build the authorization URL; codes are exchanged through the Transport,
+        // like every other token request.
         let oauth_client = BasicClient::new(
             ClientId::new(credentials.client_id.clone()),
-            Some(ClientSecret::new(credentials.client_secret.clone())),
-            AuthUrl::new(credentials.auth_url.clone()).expect("Failed to parse auth URL"),
-            Some(TokenUrl::new(credentials.token_url.clone()).expect("Failed to parse token URL")),
-        )
-        .set_redirect_uri(
-            RedirectUrl::new("http://localhost:8080/".to_owned()).expect("Invalid redirect URL"),
+            None,
+            AuthUrl::new(credentials.auth_url.clone()).map_err(invalid)?,
+            None,
         );
 
-        ZohoClient {
+        Ok(ZohoClient {
             oauth_client,
             credentials,
-        }
+            transport,
+            retry_policy,
+        })
     }
 
-    pub(in crate::oauth) fn request_access(&mut self) {
-        let (authorize_url, csrf_state) = self
+    fn redirect_url(&self) -> Result<RedirectUrl> {
+        RedirectUrl::new(self.credentials.redirect_uri.clone())
+            .map_err(|e| Error::oauth(OAuthErrorKind::InvalidConfiguration, &e.to_string()))
+    }
+
+    // Build the URL to send the user to, along with the state to expect back and, when using
+    // PKCE, the verifier to present when exchanging the code.
+    fn authorize_url(&self) -> Result<(Url, CsrfToken, Option<PkceCodeVerifier>)> {
+        let mut request = self
             .oauth_client
             .authorize_url(CsrfToken::new_random)
-            .add_scope(Scope::new("ZohoProjects.portals.READ".to_string()))
-            .add_scope(Scope::new("ZohoProjects.projects.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.events.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.bugs.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.status.READ".to_string()))
-            .add_scope(Scope::new("ZohoProjects.status.CREATE".to_string()))
-            .add_scope(Scope::new("ZohoProjects.forums.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.milestones.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.tasks.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.tasklists.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.users.ALL".to_string()))
-            .add_scope(Scope::new("ZohoProjects.documents.READ".to_string()))
-            .add_scope(Scope::new("ZohoProjects.documents.CREATE".to_string()))
-            .add_scope(Scope::new("ZohoProjects.documents.UPDATE".to_string()))
-            .add_scope(Scope::new("ZohoProjects.documents.DELETE".to_string()))
-            .add_scope(Scope::new("ZohoPC.files.READ".to_string()))
-            .add_scope(Scope::new("ZohoPC.files.CREATE".to_string()))
-            .add_scope(Scope::new("ZohoPC.files.DELETE".to_string()))
-            // .set_pkce_challenge(pkce_challenge)
-            .url();
+            .set_redirect_uri(Cow::Owned(self.redirect_url()?))
+            .add_scopes(
+                self.credentials
+                    .scopes
+                    .iter()
+                    .map(|s| Scope::new(s.to_owned())),
+            );
+
+        let pkce_verifier = if self.credentials.uses_pkce() {
+            let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+            request = request.set_pkce_challenge(pkce_challenge);
+            Some(pkce_verifier)
+        } else {
+            None
+        };
+
+        let (url, csrf_state) = request.url();
+        Ok((url, csrf_state, pkce_verifier))
+    }
+
+    pub(in crate::oauth) fn request_access(&mut self) -> Result<()> {
+        let (authorize_url, csrf_state, pkce_verifier) = self.authorize_url()?;
+
+        let listener = TcpListener::bind(self.credentials.listen_addr()?)
+            .map_err(|e| Error::oauth(OAuthErrorKind::Listener, &e.to_string()))?;
 
         if webbrowser::open(authorize_url.as_str()).is_ok() {
             debug!("Opened browser successfully")
@@ -142,105 +584,708 @@ impl ZohoClient {
             println!("Open this URL in your browser:\n{}\n", authorize_url);
         }
 
-        let listener = TcpListener::bind("127.0.0.1:8080").unwrap();
-        for stream in listener.incoming() {
-            if stream.is_err() {
-                debug!("Failed to read stream");
-                continue;
+        // The server will terminate itself after collecting the first redirect.
+        let mut stream = loop {
+            match listener.accept() {
+                Ok((stream, _)) => break stream,
+                Err(e) => debug!("Failed to read stream: {}", e),
             }
+        };
+
+        let now = SystemTime::now();
+        let url = {
+            let mut reader = BufReader::new(&stream);
+
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .map_err(|e| Error::oauth(OAuthErrorKind::InvalidRedirect, &e.to_string()))?;
+
+            let redirect_url = request_line.split_whitespace().nth(1).ok_or_else(|| {
+                Error::oauth(OAuthErrorKind::InvalidRedirect, request_line.trim())
+            })?;
+            Url::parse(&("http://localhost".to_string() + redirect_url))
+                .map_err(|e| Error::oauth(OAuthErrorKind::InvalidRedirect, &e.to_string()))?
+        };
+        let outcome = redirect_params(&url).and_then(|(code, state)| {
+            check_state(&state, &csrf_state)?;
+            Ok(code)
+        });
+
+        // Write a minimal HTTP response to the browser to tell the user how it went.
+        let message = match outcome {
+            Ok(_) => "Authenticated successfully. You can now close this tab.".to_owned(),
+            Err(ref err) => format!("Authentication failed: {}", err),
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            message.len(),
+            message
+        );
+        if let Err(e) = stream.write_all(response.as_bytes()) {
+            debug!("Failed to respond to browser: {}", e);
+        }
+
+        // Exchange the code with a token.
+        let token_response =
+            self.exchange_code(outcome?, Some(self.redirect_url()?), pkce_verifier)?;
+        self.credentials.update(token_response, now)
+    }
 
-            let mut stream = stream.unwrap();
-
-            let code;
-            let state;
-            let now = SystemTime::now();
-            {
-                let mut reader = BufReader::new(&stream);
-
-                let mut request_line = String::new();
-                reader.read_line(&mut request_line).unwrap();
-
-                let redirect_url = request_line.split_whitespace().nth(1).unwrap();
-                let url = Url::parse(&("http://localhost".to_string() + redirect_url)).unwrap();
-
-                let code_pair = url
-                    .query_pairs()
-                    .find(|pair| {
-                        let (ref key, _) = pair;
-                        key == "code"
-                    })
-                    .unwrap();
-
-                let (_, value) = code_pair;
-                code = AuthorizationCode::new(value.into_owned());
-
-                let state_pair = url
-                    .query_pairs()
-                    .find(|pair| {
-                        let (ref key, _) = pair;
-                        key == "state"
-                    })
-                    .unwrap();
-
-                let (_, value) = state_pair;
-                state = CsrfToken::new(value.into_owned());
+    // For when the user carries the code over by hand, from a browser that may be on another
+    // machine entirely. The response may be the full redirected URL or just the code.
+    pub(in crate::oauth) fn request_access_manually(
+        &mut self,
+        read_response: impl FnOnce(&str) -> String,
+    ) -> Result<()> {
+        let (authorize_url, csrf_state, pkce_verifier) = self.authorize_url()?;
+        let response = read_response(authorize_url.as_str());
+        let now = SystemTime::now();
+
+        let code = match Url::parse(response.trim()) {
+            Ok(url) => {
+                let (code, state) = redirect_params(&url)?;
+                check_state(&state, &csrf_state)?;
+                code
+            }
+            Err(_) if !response.trim().is_empty() => {
+                AuthorizationCode::new(response.trim().to_owned())
             }
+            Err(_) => {
+                return Err(Error::oauth(
+                    OAuthErrorKind::MissingCode,
+                    "no code was entered",
+                ))
+            }
+        };
 
-            // Write a minimal HTTP response to the browser to reassure the user.
-            let message = "Authenticated successfully. You can now close this tab.";
-            let response = format!(
-                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
-                message.len(),
-                message
-            );
-            stream.write_all(response.as_bytes()).unwrap();
-
-            if state.secret() != csrf_state.secret() {
-                println!(
-                    "CSRF Tokens appear to differ. Please check:\n{:?}\n{:?}",
-                    state.secret(),
-                    csrf_state.secret()
-                )
+        let token_response = self.exchange_code(code, Some(self.redirect_url()?), pkce_verifier)?;
+        self.credentials.update(token_response, now)
+    }
+
+    // Self Client grant codes are generated in the API console rather than by a redirect, so
+    // no redirect URI is involved in exchanging them.
+    pub(in crate::oauth) fn exchange_grant_code(&mut self, grant_code: &str) -> Result<()> {
+        let now = SystemTime::now();
+        let token_response =
+            self.exchange_code(AuthorizationCode::new(grant_code.to_owned()), None, None)?;
+        self.credentials.update(token_response, now)
+    }
+
+    // Exchange a code for tokens with Zoho's authorization_code grant, presenting the PKCE
+    // verifier if a challenge was sent with the authorization request.
+    fn exchange_code(
+        &self,
+        code: AuthorizationCode,
+        redirect_url: Option<RedirectUrl>,
+        pkce_verifier: Option<PkceCodeVerifier>,
+    ) -> Result<ZohoTokenResponse> {
+        let credentials = &self.credentials;
+        let mut params = vec![
+            ("grant_type".to_owned(), "authorization_code".to_owned()),
+            ("code".to_owned(), code.secret().clone()),
+            ("client_id".to_owned(), credentials.client_id.clone()),
+        ];
+        if let Some(ref client_secret) = credentials.client_secret {
+            params.push(("client_secret".to_owned(), client_secret.clone()));
+        }
+        if let Some(redirect_url) = redirect_url {
+            params.push(("redirect_uri".to_owned(), redirect_url.as_str().to_owned()));
+        }
+        if let Some(pkce_verifier) = pkce_verifier {
+            params.push(("code_verifier".to_owned(), pkce_verifier.secret().clone()));
+        }
+
+        request_token(
+            self.transport,
+            &self.retry_policy,
+            &credentials.token_url,
+            params,
+        )
+    }
+
+    pub(in crate::oauth) fn credentials(&self) -> Credentials {
+        self.credentials.clone()
+    }
+}
+
+// POST to the token endpoint and parse what comes back, retrying as the policy allows.
+// Zoho reports most failures with a success status and an `error` field, which is left for the
+// caller to surface; failing to reach Zoho, or any other status, is an error here.
+// The parameters go in a form body rather than the query string, to keep the secrets they
+// carry out of proxy and access logs.
+fn request_token(
+    transport: &dyn Transport,
+    retry_policy: &RetryPolicy,
+    token_url: &str,
+    params: Vec<(String, String)>,
+) -> Result<ZohoTokenResponse> {
+    let request = HttpRequest::new(Method::POST, token_url).body(RequestBody::Form(params));
+    let mut attempts = 0;
+    let response = loop {
+        attempts += 1;
+        let outcome = transport.send(&request);
+        match retry_policy.retry_wait(&request.method, attempts, &outcome) {
+            Some(wait) => {
+                warn!(
+                    "Zoho token request failed on attempt {}; retrying in {:?}",
+                    attempts, wait
+                );
+                thread::sleep(wait);
             }
+            None => break outcome,
+        }
+    };
+
+    let response =
+        response.map_err(|e| Error::oauth(OAuthErrorKind::TokenRequest, &e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(Error::oauth(
+            OAuthErrorKind::TokenRequest,
+            &response.status().to_string(),
+        ));
+    }
+
+    response
+        .json()
+        .map_err(|e| Error::oauth(OAuthErrorKind::TokenRequest, &e.to_string()))
+}
+
+// POST a token to the revoke endpoint, again in a form body. As with token requests, Zoho may
+// report a failure with a success status and an `error` field.
+fn revoke_token(transport: &dyn Transport, revoke_url: &str, token: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct RevokeResponse {
+        error: Option<String>,
+    }
+
+    let request = HttpRequest::new(Method::POST, revoke_url).body(RequestBody::Form(vec![(
+        "token".to_owned(),
+        token.to_owned(),
+    )]));
+    let response = transport.send(&request)?;
+    if !response.status().is_success() {
+        return Err(Error::oauth(
+            OAuthErrorKind::Revocation,
+            &response.status().to_string(),
+        ));
+    }
+
+    match response.json::<RevokeResponse>() {
+        Ok(RevokeResponse { error: Some(error) }) => {
+            Err(Error::oauth(OAuthErrorKind::Revocation, &error))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::oauth(OAuthErrorKind::Revocation, &e.to_string())),
+    }
+}
+
+// Pull the code and state out of the URL Zoho redirected to after authorization.
+fn redirect_params(url: &Url) -> Result<(AuthorizationCode, CsrfToken)> {
+    let value_of = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    if let Some(error) = value_of("error") {
+        return Err(Error::oauth(OAuthErrorKind::InvalidRedirect, &error));
+    }
+
+    let code =
+        value_of("code").ok_or_else(|| Error::oauth(OAuthErrorKind::MissingCode, url.as_str()))?;
+    let state = value_of("state")
+        .ok_or_else(|| Error::oauth(OAuthErrorKind::MissingState, url.as_str()))?;
+
+    Ok((AuthorizationCode::new(code), CsrfToken::new(state)))
+}
+
+fn check_state(returned: &CsrfToken, sent: &CsrfToken) -> Result<()> {
+    if returned.secret() != sent.secret() {
+        return Err(Error::oauth(
+            OAuthErrorKind::CsrfMismatch,
+            "state returned with the authorization code does not match the one sent",
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_stdin(authorize_url: &str) -> String {
+    println!(
+        "Open this URL in your browser:\n{}\n\nThen paste the URL you were redirected to, or the code from it:",
+        authorize_url
+    );
+
+    let mut line = String::new();
+    if let Err(err) = std::io::stdin().read_line(&mut line) {
+        warn!("Failed to read authorization code from stdin: {}", err);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_store::MemoryTokenStore;
+    use crate::transport::MockTransport;
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const TOKEN: &str = r#"{"access_token": "new-access", "expires_in": 3600}"#;
+
+    fn credentials() -> Credentials {
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.refresh_token = Some("refresh".to_owned());
+        credentials
+    }
+
+    // Authorization which records that it was attempted, then gives up for want of a code.
+    fn authorization_attempted(credentials: &mut Credentials) -> Arc<AtomicBool> {
+        let attempted = Arc::new(AtomicBool::new(false));
+        let flag = attempted.clone();
+        credentials.set_auth_mode(AuthMode::Callback(Arc::new(move |_| {
+            flag.store(true, Ordering::SeqCst);
+            String::new()
+        })));
+        attempted
+    }
+
+    fn kind(result: Result<String>) -> OAuthErrorKind {
+        match result {
+            Err(Error::OAuth { kind, .. }) => kind,
+            other => panic!("expected an OAuth error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn debug_hides_secrets() {
+        let mut credentials = credentials();
+        credentials.access_token = Some("access".to_owned());
+        let printed = format!("{:?}", credentials);
+
+        assert!(printed.contains("client-id"));
+        assert!(printed.contains("client_secret: Some(..)"));
+        assert!(printed.contains("access_token: Some(..)"));
+        assert!(printed.contains("refresh_token: Some(..)"));
+        assert!(!printed.contains("client-secret"));
+        assert!(!printed.contains("\"access\""));
+        assert!(!printed.contains("\"refresh\""));
+    }
+
+    #[test]
+    fn store_tokens_are_adopted_and_saved() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, TOKEN);
+        let store = MemoryTokenStore::with_tokens(StoredTokens {
+            refresh_token: Some("stored-refresh".to_owned()),
+            ..StoredTokens::default()
+        });
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_store(Arc::new(store.clone())).unwrap();
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "new-access");
+        let body = transport.requests()[0].body.clone().expect("a form body");
+        assert_eq!(body.field("refresh_token"), Some("stored-refresh"));
+
+        let saved = store.load().unwrap().expect("tokens saved");
+        assert_eq!(saved.access_token.as_deref(), Some("new-access"));
+        assert_eq!(saved.refresh_token.as_deref(), Some("stored-refresh"));
+        assert!(saved.expires_at.is_some());
+    }
+
+    #[test]
+    fn refresh_sends_a_form_body() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, TOKEN);
+        let mut credentials = credentials();
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "new-access");
+        // Zoho doesn't send a new refresh token, so the old one is kept.
+        assert_eq!(credentials.refresh_token.as_deref(), Some("refresh"));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::POST);
+        assert_eq!(requests[0].url, "https://accounts.zoho.com/oauth/v2/token");
+        assert!(requests[0].query.is_empty());
+        let body = requests[0].body.as_ref().expect("a form body");
+        assert_eq!(body.field("grant_type"), Some("refresh_token"));
+        assert_eq!(body.field("refresh_token"), Some("refresh"));
+        assert_eq!(body.field("client_id"), Some("client-id"));
+        assert_eq!(body.field("client_secret"), Some("client-secret"));
+    }
+
+    #[test]
+    fn current_token_is_not_refreshed() {
+        let transport = MockTransport::new();
+        let mut credentials = credentials();
+        credentials.access_token = Some("current".to_owned());
+        credentials.access_token_expiry = Some(SystemTime::now() + Duration::from_secs(3600));
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "current");
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn refused_refresh_falls_back_to_authorization() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, r#"{"error": "invalid_code"}"#);
+        let mut credentials = credentials();
+        let attempted = authorization_attempted(&mut credentials);
+
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(kind(result), OAuthErrorKind::MissingCode);
+        assert!(attempted.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn failed_refresh_does_not_fall_back() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::SERVICE_UNAVAILABLE, "");
+        let mut credentials = credentials();
+        let attempted = authorization_attempted(&mut credentials);
+
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(kind(result), OAuthErrorKind::TokenRequest);
+        // With nothing queued, the transport itself fails.
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(kind(result), OAuthErrorKind::TokenRequest);
+        assert!(!attempted.load(Ordering::SeqCst));
+        assert_eq!(credentials.refresh_token.as_deref(), Some("refresh"));
+    }
+
+    #[test]
+    fn refresh_is_retried_under_the_policy() {
+        let transport = MockTransport::new();
+        transport
+            .respond(StatusCode::TOO_MANY_REQUESTS, "")
+            .respond(StatusCode::OK, TOKEN);
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_millis(1));
+
+        let token = credentials().access_token(&transport, &policy);
+        assert_eq!(token.unwrap(), "new-access");
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn read_only_scopes_cannot_write() {
+        let scopes = Scopes::read_only();
+        assert!(scopes.iter().all(|scope| scope.ends_with(".READ")));
+        assert!(Scopes::full()
+            .iter()
+            .any(|scope| scope == "ZohoPC.files.DELETE"));
+
+        let scopes = scopes
+            .with("ZohoProjects.tasks.ALL")
+            .with("ZohoProjects.tasks.ALL");
+        let added = scopes.iter().filter(|scope| scope.ends_with(".ALL"));
+        assert_eq!(added.count(), 1);
+    }
+
+    #[test]
+    fn listener_follows_the_redirect_port() {
+        let mut credentials = credentials();
+        assert_eq!(credentials.listen_addr().unwrap(), "127.0.0.1:8080");
+
+        credentials.set_redirect_uri("http://localhost:9090/callback");
+        assert_eq!(credentials.listen_addr().unwrap(), "127.0.0.1:9090");
+
+        credentials.set_listen_addr("0.0.0.0:7000");
+        assert_eq!(credentials.listen_addr().unwrap(), "0.0.0.0:7000");
+    }
+
+    fn query(url: &Url, key: &str) -> Option<String> {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    // Authorization which approves whatever is asked, returning the redirect Zoho would send
+    // along with the authorization URL it was given.
+    fn approve(credentials: &mut Credentials) -> Arc<std::sync::Mutex<Option<Url>>> {
+        let authorize_url = Arc::new(std::sync::Mutex::new(None));
+        let seen = authorize_url.clone();
+        credentials.set_auth_mode(AuthMode::Callback(Arc::new(move |url| {
+            let url = Url::parse(url).unwrap();
+            let state = query(&url, "state").unwrap();
+            *seen.lock().unwrap() = Some(url);
+            format!("http://localhost:8080/?code=the-code&state={}", state)
+        })));
+        authorize_url
+    }
+
+    #[test]
+    fn authorization_url_carries_a_pkce_challenge() {
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_pkce(true);
+        let transport = MockTransport::new();
+        let client = ZohoClient::new(credentials, &transport, RetryPolicy::none()).unwrap();
+
+        let (url, state, verifier) = client.authorize_url().unwrap();
+        let verifier = verifier.expect("a PKCE verifier");
+        let challenge = PkceCodeChallenge::from_code_verifier_sha256(&verifier);
+        assert_eq!(
+            query(&url, "code_challenge").as_deref(),
+            Some(challenge.as_str())
+        );
+        assert_eq!(
+            query(&url, "code_challenge_method").as_deref(),
+            Some("S256")
+        );
+        assert_eq!(
+            query(&url, "state").as_deref(),
+            Some(state.secret().as_str())
+        );
+        assert_eq!(query(&url, "client_id").as_deref(), Some("client-id"));
+        assert_eq!(
+            query(&url, "redirect_uri").as_deref(),
+            Some("http://localhost:8080/")
+        );
+        assert_eq!(query(&url, "client_secret"), None);
+    }
+
+    #[test]
+    fn pkce_is_only_used_by_default_without_a_secret() {
+        let transport = MockTransport::new();
+        let confidential = Credentials::new("client-id", Some("client-secret"), None, None);
+        let client = ZohoClient::new(confidential, &transport, RetryPolicy::none()).unwrap();
+        let (url, _, verifier) = client.authorize_url().unwrap();
+        assert!(verifier.is_none());
+        assert_eq!(query(&url, "code_challenge"), None);
 
-            // Exchange the code with a token.
-            let token_response = self.exchange_code(code);
+        let public = Credentials::new("client-id", None, None, None);
+        let client = ZohoClient::new(public, &transport, RetryPolicy::none()).unwrap();
+        let (url, _, verifier) = client.authorize_url().unwrap();
+        assert!(verifier.is_some());
+        assert!(query(&url, "code_challenge").is_some());
+    }
+
+    #[test]
+    fn code_is_exchanged_through_the_transport() {
+        let transport = MockTransport::new();
+        transport.respond(
+            StatusCode::OK,
+            r#"{"access_token": "new-access", "refresh_token": "new-refresh", "expires_in": 3600}"#,
+        );
+        let mut credentials = Credentials::new("client-id", None, None, None);
+        let authorize_url = approve(&mut credentials);
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "new-access");
+        assert_eq!(credentials.refresh_token.as_deref(), Some("new-refresh"));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url, "https://accounts.zoho.com/oauth/v2/token");
+        let body = requests[0].body.as_ref().expect("a form body");
+        assert_eq!(body.field("grant_type"), Some("authorization_code"));
+        assert_eq!(body.field("code"), Some("the-code"));
+        assert_eq!(body.field("client_id"), Some("client-id"));
+        assert_eq!(body.field("client_secret"), None);
+        assert_eq!(body.field("redirect_uri"), Some("http://localhost:8080/"));
+
+        // The verifier sent is the one the challenge was made from.
+        let verifier = PkceCodeVerifier::new(body.field("code_verifier").unwrap().to_owned());
+        let authorize_url = authorize_url.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            query(&authorize_url, "code_challenge").as_deref(),
+            Some(PkceCodeChallenge::from_code_verifier_sha256(&verifier).as_str())
+        );
+    }
+
+    #[test]
+    fn self_client_code_is_exchanged_without_a_redirect() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, TOKEN);
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_auth_mode(AuthMode::SelfClient("grant-code".to_owned()));
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "new-access");
+
+        let body = transport.requests()[0].body.clone().expect("a form body");
+        assert_eq!(body.field("grant_type"), Some("authorization_code"));
+        assert_eq!(body.field("code"), Some("grant-code"));
+        assert_eq!(body.field("client_secret"), Some("client-secret"));
+        assert_eq!(body.field("redirect_uri"), None);
+        assert_eq!(body.field("code_verifier"), None);
+    }
+
+    #[test]
+    fn manual_entry_accepts_a_bare_code() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, TOKEN);
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_auth_mode(AuthMode::Callback(Arc::new(|_| "  the-code\n".to_owned())));
+
+        let token = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(token.unwrap(), "new-access");
+
+        let body = transport.requests()[0].body.clone().expect("a form body");
+        assert_eq!(body.field("code"), Some("the-code"));
+        assert_eq!(body.field("redirect_uri"), Some("http://localhost:8080/"));
+    }
 
-            self.credentials.access_token = token_response.access_token;
-            self.credentials.access_token_expiry =
-                Some(now + Duration::from_secs(token_response.expires_in_sec as u64));
-            self.credentials.refresh_token = token_response.refresh_token;
+    #[test]
+    fn refused_code_is_an_error() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, r#"{"error": "invalid_code"}"#);
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        approve(&mut credentials);
 
-            // The server will terminate itself after collecting the first code.
-            break;
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        match result {
+            Err(Error::OAuth { kind, detail }) => {
+                assert_eq!(kind, OAuthErrorKind::TokenRequest);
+                assert_eq!(detail, "invalid_code");
+            }
+            other => panic!("expected a refused token request, got {:?}", other),
+        }
+    }
+
+    fn redirect_error(redirect: &str) -> OAuthErrorKind {
+        match redirect_params(&Url::parse(redirect).unwrap()) {
+            Err(Error::OAuth { kind, .. }) => kind,
+            other => panic!("expected an OAuth error, got {:?}", other),
         }
     }
 
-    fn exchange_code(&self, code: AuthorizationCode) -> ZohoTokenResponse {
-        let req_client = reqwest::blocking::Client::new();
-        let mut builder =
-            req_client.request(reqwest::Method::POST, self.credentials.token_url.clone());
-        let mut params = HashMap::new();
-        params.insert("code".to_owned(), code.secret().to_owned());
-        params.insert(
-            "redirect_uri".to_owned(),
-            "http://localhost:8080/".to_owned(),
+    #[test]
+    fn incomplete_redirects_are_errors() {
+        assert_eq!(
+            redirect_error("http://localhost:8080/?error=access_denied"),
+            OAuthErrorKind::InvalidRedirect
         );
-        params.insert("client_id".to_owned(), self.credentials.client_id.clone());
-        params.insert(
-            "client_secret".to_owned(),
-            self.credentials.client_secret.clone(),
+        assert_eq!(
+            redirect_error("http://localhost:8080/?state=abc"),
+            OAuthErrorKind::MissingCode
         );
-        params.insert("grant_type".to_owned(), "authorization_code".to_owned());
+        assert_eq!(
+            redirect_error("http://localhost:8080/?code=the-code"),
+            OAuthErrorKind::MissingState
+        );
+    }
 
-        builder = builder.query(&params);
+    #[test]
+    fn mismatched_state_is_refused() {
+        let transport = MockTransport::new();
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_auth_mode(AuthMode::Callback(Arc::new(|_| {
+            "http://localhost:8080/?code=the-code&state=forged".to_owned()
+        })));
 
-        // TODO(Xymist): Error handling
-        let token_response: ZohoTokenResponse = builder.send().unwrap().json().unwrap();
-        token_response
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(kind(result), OAuthErrorKind::CsrfMismatch);
+        assert!(transport.requests().is_empty());
     }
 
-    pub(in crate::oauth) fn credentials(&self) -> Credentials {
-        self.credentials.clone()
+    #[test]
+    fn invalid_redirect_uri_is_a_configuration_error() {
+        let transport = MockTransport::new();
+        let mut credentials = Credentials::new("client-id", Some("client-secret"), None, None);
+        credentials.set_redirect_uri("not a url");
+        approve(&mut credentials);
+
+        let result = credentials.access_token(&transport, &RetryPolicy::none());
+        assert_eq!(kind(result), OAuthErrorKind::InvalidConfiguration);
+        assert!(transport.requests().is_empty());
+    }
+
+    // Credentials holding a current access token and a refresh token, saved in the store.
+    fn revocable(store: &MemoryTokenStore) -> Credentials {
+        let mut credentials = credentials();
+        credentials.access_token = Some("access".to_owned());
+        credentials.access_token_expiry = Some(SystemTime::now() + Duration::from_secs(3600));
+        credentials.set_store(Arc::new(store.clone())).unwrap();
+        credentials.persist();
+        credentials
+    }
+
+    fn revoked_token(request: &HttpRequest) -> Option<&str> {
+        request.body.as_ref().and_then(|body| body.field("token"))
+    }
+
+    #[test]
+    fn revoke_clears_tokens_and_store() {
+        let transport = MockTransport::new();
+        transport
+            .respond(StatusCode::OK, "{}")
+            .respond(StatusCode::OK, "{}");
+        let store = MemoryTokenStore::new();
+        let mut credentials = revocable(&store);
+
+        let revoked = credentials.revoke(&transport).unwrap();
+        assert_eq!(
+            revoked,
+            RevokedTokens {
+                refresh_token: true,
+                access_token: true,
+            }
+        );
+        assert_eq!(credentials.access_token, None);
+        assert_eq!(credentials.refresh_token, None);
+        assert_eq!(store.load().unwrap(), None);
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].url,
+            "https://accounts.zoho.com/oauth/v2/token/revoke"
+        );
+        assert_eq!(revoked_token(&requests[0]), Some("refresh"));
+        assert_eq!(revoked_token(&requests[1]), Some("access"));
+    }
+
+    #[test]
+    fn refused_refresh_revocation_keeps_tokens() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, r#"{"error": "invalid_token"}"#);
+        let store = MemoryTokenStore::new();
+        let mut credentials = revocable(&store);
+
+        match credentials.revoke(&transport) {
+            Err(Error::OAuth { kind, .. }) => assert_eq!(kind, OAuthErrorKind::Revocation),
+            other => panic!("expected a refused revocation, got {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(credentials.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(credentials.access_token.as_deref(), Some("access"));
+        let stored = store.load().unwrap().expect("tokens still stored");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh"));
+    }
+
+    #[test]
+    fn refused_access_revocation_after_refresh_is_not_fatal() {
+        let transport = MockTransport::new();
+        transport
+            .respond(StatusCode::OK, "{}")
+            .respond(StatusCode::BAD_REQUEST, "");
+        let store = MemoryTokenStore::new();
+        let mut credentials = revocable(&store);
+
+        let revoked = credentials.revoke(&transport).unwrap();
+        assert_eq!(
+            revoked,
+            RevokedTokens {
+                refresh_token: true,
+                access_token: false,
+            }
+        );
+        assert_eq!(credentials.access_token, None);
+        assert_eq!(credentials.refresh_token, None);
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[test]
+    fn refused_access_revocation_alone_keeps_the_token() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::OK, r#"{"error": "invalid_token"}"#);
+        let mut credentials = Credentials::with_access_token("access");
+
+        assert!(credentials.revoke(&transport).is_err());
+        assert_eq!(credentials.access_token.as_deref(), Some("access"));
     }
 }
@@ -0,0 +1,498 @@
+//! Iteration across every record of a list endpoint, fetching a page at a time. The Zoho API
+//! returns at most 100 records per request; a Paginator requests successive pages by index
+//! until one comes back short, then moves on to any records nested beneath those returned.
+
+use crate::errors::*;
+use crate::request::{Collection, FilterOptions, ModelOf, ModelRequest, RequestParameters};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Zoho refuses ranges above 100.
+const MAX_PAGE_SIZE: u8 = 100;
+
+/// A position within a paginated listing, which can be saved and later handed back to `resume`
+/// to continue from the first record not yet returned.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Index of the next record to fetch
+    pub start_index: usize,
+    /// The record whose nested records are being paged through, e.g. the Task whose subtasks
+    /// are, or None for the request's own listing
+    pub parent_id: Option<i64>,
+    /// Records whose nested records are still to be fetched, in the order they will be, e.g.
+    /// Tasks whose subtasks are
+    pub subtask_parent_ids: Vec<i64>,
+}
+
+/// Iterator over every record matching a request, however many pages they span. Created with
+/// the `iter_get` method of each list request, e.g. `project.bugs().iter_get()`. Records
+/// nested beneath those returned, such as subtasks where requested, follow the rest, level
+/// by level.
+///
+/// If a page cannot be fetched, the error is yielded in place of its records and iteration
+/// ends there.
+pub struct Paginator<R>
+where
+    R: RequestParameters + Clone,
+{
+    // Nested listings are found relative to the original request, not the one being paged.
+    base: R,
+    request: R,
+    // The record whose nested records `request` lists, if it isn't the top level.
+    parent_id: Option<i64>,
+    // Records whose nested records are still to come, in the order they were returned.
+    pending: VecDeque<i64>,
+    items: std::vec::IntoIter<ModelOf<R>>,
+    start_index: usize,
+    page_size: u8,
+    max_items: Option<usize>,
+    yielded: usize,
+    finished: bool,
+}
+
+impl<R> Paginator<R>
+where
+    R: RequestParameters + Clone,
+{
+    /// Constructor for a Paginator over the given request. Pages are the size of any Range
+    /// filter already set on it, or 100.
+    pub fn new(request: R) -> Self {
+        Paginator {
+            page_size: page_size_of(&request),
+            base: request.clone(),
+            request,
+            parent_id: None,
+            pending: VecDeque::new(),
+            items: Vec::new().into_iter(),
+            start_index: 0,
+            max_items: None,
+            yielded: 0,
+            finished: false,
+        }
+    }
+
+    /// Fetch this many records per request, up to Zoho's limit of 100
+    pub fn page_size(mut self, page_size: u8) -> Self {
+        self.page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// Stop after this many records in total, counting nested records, requesting no more
+    /// than needed to reach it
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Continue from a Cursor saved earlier, rather than the first record
+    pub fn resume(mut self, cursor: Cursor) -> Self {
+        self.request = cursor
+            .parent_id
+            .and_then(|parent_id| self.base.nested(parent_id))
+            .unwrap_or_else(|| self.base.clone());
+        self.parent_id = cursor.parent_id;
+        self.pending = cursor.subtask_parent_ids.into_iter().collect();
+        self.items = Vec::new().into_iter();
+        self.start_index = cursor.start_index;
+        self.yielded = 0;
+        self.finished = false;
+        self
+    }
+
+    /// The position of the next record to be returned, including any nested listings still
+    /// to be fetched, to `resume` from later. Records already fetched but not yet returned are
+    /// fetched again on resumption.
+    pub fn cursor(&self) -> Cursor {
+        let mut pending = self.pending.iter().copied();
+        if self.finished && self.items.len() == 0 {
+            if let Some(parent_id) = pending.next() {
+                // The current listing is done with, so start from the next.
+                return Cursor {
+                    start_index: 0,
+                    parent_id: Some(parent_id),
+                    subtask_parent_ids: pending.collect(),
+                };
+            }
+        }
+
+        Cursor {
+            start_index: self.start_index - self.items.len(),
+            parent_id: self.parent_id,
+            subtask_parent_ids: pending.collect(),
+        }
+    }
+
+    /// Accessor method for the request being paged through, which is the request for the
+    /// nested records currently being listed once those of the original run out
+    pub fn request(&self) -> &R {
+        &self.request
+    }
+
+    /// Fetch every remaining record, failing if any page cannot be fetched
+    pub fn try_collect_all(self) -> Result<Vec<ModelOf<R>>> {
+        self.collect()
+    }
+
+    /// Return the next record, fetching the next page first if the current one is used up.
+    /// Returns Ok(None) once every record has been returned.
+    pub fn try_next(&mut self) -> Result<Option<ModelOf<R>>> {
+        loop {
+            if let Some(item) = self.take_fetched() {
+                return Ok(Some(item));
+            }
+            if let Some(request) = self.next_page() {
+                self.receive(request.get())?;
+            } else if !self.next_listing() {
+                return Ok(None);
+            }
+        }
+    }
+
+    // The blocking and async paginators, and TaskIterator, share the steps below, differing
+    // only in how they send the request for each page.
+
+    // Take the next record already fetched, if the limit allows another, noting it if it has
+    // records nested beneath it to fetch later.
+    pub(crate) fn take_fetched(&mut self) -> Option<ModelOf<R>> {
+        if self.exhausted() {
+            return None;
+        }
+
+        let item = self.items.next()?;
+        self.yielded += 1;
+        if let Some(parent_id) = self.base.parent_id(&item) {
+            self.pending.push_back(parent_id);
+        }
+        Some(item)
+    }
+
+    // The request for the next page, if the records fetched are used up and more are wanted.
+    pub(crate) fn next_page(&self) -> Option<R> {
+        if self.exhausted() || self.finished || self.items.len() > 0 {
+            return None;
+        }
+
+        Some(self.page_of(self.request.clone(), self.start_index))
+    }
+
+    // Take in the page requested from next_page. An error ends the pagination, including any
+    // nested listings still to come.
+    pub(crate) fn receive(&mut self, page: Result<R::ModelCollection>) -> Result<()> {
+        let items = match page {
+            Ok(page) => page.into_models(),
+            Err(err) => {
+                self.finished = true;
+                self.pending.clear();
+                return Err(err);
+            }
+        };
+
+        // A short page means there are no more to come.
+        self.finished = items.len() < usize::from(self.range());
+        self.start_index += items.len();
+        self.items = items.into_iter();
+        Ok(())
+    }
+
+    // Move on to the next nested listing, once the current one is used up. Returns false if
+    // there are none left, or no more records are wanted.
+    pub(crate) fn next_listing(&mut self) -> bool {
+        if self.exhausted() {
+            return false;
+        }
+
+        while let Some(parent_id) = self.pending.pop_front() {
+            if let Some(request) = self.base.nested(parent_id) {
+                self.request = request;
+                self.parent_id = Some(parent_id);
+                self.items = Vec::new().into_iter();
+                self.start_index = 0;
+                self.finished = false;
+                return true;
+            }
+        }
+        false
+    }
+
+    // The record whose nested records are about to be listed from the start, if the next page
+    // is the first of a nested listing, so that it may have been fetched ahead.
+    pub(crate) fn starting_listing(&self) -> Option<i64> {
+        self.parent_id
+            .filter(|_| self.start_index == 0 && self.next_page().is_some())
+    }
+
+    // The records whose nested records are still to be listed, after the current listing.
+    pub(crate) fn pending(&self) -> impl Iterator<Item = i64> + '_ {
+        self.pending.iter().copied()
+    }
+
+    // The request for the first page of the records nested beneath this one, to fetch ahead.
+    pub(crate) fn first_page_of(&self, parent_id: i64) -> Option<R> {
+        Some(self.page_of(self.base.nested(parent_id)?, 0))
+    }
+
+    fn page_of(&self, request: R, start_index: usize) -> R {
+        request
+            .filter(Page::Index(start_index))
+            .filter(Page::Range(self.range()))
+    }
+
+    fn exhausted(&self) -> bool {
+        self.max_items.is_some_and(|max| self.yielded >= max)
+    }
+
+    // How many records to ask for in the next page.
+    fn range(&self) -> u8 {
+        match self.max_items {
+            Some(max) => self
+                .page_size
+                .min(max.saturating_sub(self.yielded).min(255) as u8),
+            None => self.page_size,
+        }
+    }
+}
+
+impl<R> Iterator for Paginator<R>
+where
+    R: RequestParameters + Clone,
+{
+    type Item = Result<ModelOf<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(Some(val)) => Some(Ok(val)),
+            Ok(None) => None,
+            // try_next has already marked the Paginator finished, so this is the last item.
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<R> Clone for Paginator<R>
+where
+    R: RequestParameters + Clone,
+    ModelOf<R>: Clone,
+{
+    fn clone(&self) -> Self {
+        Paginator {
+            base: self.base.clone(),
+            request: self.request.clone(),
+            parent_id: self.parent_id,
+            pending: self.pending.clone(),
+            items: self.items.clone(),
+            start_index: self.start_index,
+            page_size: self.page_size,
+            max_items: self.max_items,
+            yielded: self.yielded,
+            finished: self.finished,
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for Paginator<R>
+where
+    R: RequestParameters + Clone + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Paginator")
+            .field("request", &self.request)
+            .field("parent_id", &self.parent_id)
+            .field("pending", &self.pending)
+            .field("start_index", &self.start_index)
+            .field("page_size", &self.page_size)
+            .field("max_items", &self.max_items)
+            .field("yielded", &self.yielded)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+// The size of any Range filter already set on the request, or the largest page Zoho allows.
+pub(crate) fn page_size_of(request: &impl ModelRequest) -> u8 {
+    request
+        .params()
+        .and_then(|params| params.get("range").and_then(|r| r.parse().ok()))
+        .unwrap_or(MAX_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE)
+}
+
+// Every list endpoint accepts the same pair of parameters for paging.
+pub(crate) enum Page {
+    Index(usize),
+    Range(u8),
+}
+
+impl FilterOptions for Page {
+    fn key(&self) -> String {
+        match self {
+            Page::Index(_) => "index".to_owned(),
+            Page::Range(_) => "range".to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Page::Index(index) => write!(f, "{}", index),
+            Page::Range(range) => write!(f, "{}", range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ZohoClient;
+    use crate::models::category::CategoryRequest;
+    use crate::transport::MockTransport;
+    use reqwest::StatusCode;
+
+    fn categories(transport: &MockTransport) -> CategoryRequest {
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        CategoryRequest::new(&client, "portal/1/projects/2/categories/", None)
+    }
+
+    fn page(transport: &MockTransport, ids: &[i64]) {
+        let categories: Vec<String> = ids
+            .iter()
+            .map(|id| format!(r#"{{"id": "{}", "name": "Category {}"}}"#, id, id))
+            .collect();
+        transport.respond(
+            StatusCode::OK,
+            &format!(r#"{{"categories": [{}]}}"#, categories.join(",")),
+        );
+    }
+
+    fn ids(paginator: Paginator<CategoryRequest>) -> Vec<i64> {
+        paginator
+            .try_collect_all()
+            .expect("every page fetched")
+            .iter()
+            .map(|category| category.id)
+            .collect()
+    }
+
+    fn pages_requested(transport: &MockTransport) -> Vec<(String, String)> {
+        transport
+            .requests()
+            .iter()
+            .map(|request| {
+                (
+                    request.query_param("index").unwrap_or_default().to_owned(),
+                    request.query_param("range").unwrap_or_default().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    fn pair(index: &str, range: &str) -> (String, String) {
+        (index.to_owned(), range.to_owned())
+    }
+
+    #[test]
+    fn short_page_ends_iteration() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        page(&transport, &[3]);
+
+        let paginator = Paginator::new(categories(&transport)).page_size(2);
+        assert_eq!(ids(paginator), vec![1, 2, 3]);
+        assert_eq!(
+            pages_requested(&transport),
+            vec![pair("0", "2"), pair("2", "2")]
+        );
+    }
+
+    #[test]
+    fn no_content_after_a_full_page_ends_iteration() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        transport.respond(StatusCode::NO_CONTENT, "");
+
+        let paginator = Paginator::new(categories(&transport)).page_size(2);
+        assert_eq!(ids(paginator), vec![1, 2]);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn page_size_defaults_to_the_requested_range() {
+        let transport = MockTransport::new();
+        page(&transport, &[1]);
+
+        let request = categories(&transport).filter(Page::Range(25));
+        assert_eq!(ids(Paginator::new(request)), vec![1]);
+        assert_eq!(pages_requested(&transport), vec![pair("0", "25")]);
+    }
+
+    #[test]
+    fn max_items_requests_no_more_than_needed() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        page(&transport, &[3]);
+
+        let paginator = Paginator::new(categories(&transport))
+            .page_size(2)
+            .max_items(3);
+        assert_eq!(ids(paginator), vec![1, 2, 3]);
+        assert_eq!(
+            pages_requested(&transport),
+            vec![pair("0", "2"), pair("2", "1")]
+        );
+    }
+
+    #[test]
+    fn max_items_stops_within_a_page() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2, 3]);
+
+        let paginator = Paginator::new(categories(&transport)).max_items(2);
+        assert_eq!(ids(paginator), vec![1, 2]);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn error_ends_iteration() {
+        let transport = MockTransport::new();
+        transport.respond(StatusCode::NOT_FOUND, "");
+
+        let mut paginator = Paginator::new(categories(&transport));
+        assert!(paginator.next().expect("the error").is_err());
+        assert!(paginator.next().is_none());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn cursor_round_trips_through_json() {
+        let cursor = Cursor {
+            start_index: 40,
+            parent_id: Some(7),
+            subtask_parent_ids: vec![8, 9],
+        };
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(serde_json::from_str::<Cursor>(&json).unwrap(), cursor);
+    }
+
+    #[test]
+    fn resume_continues_from_the_first_record_not_returned() {
+        let transport = MockTransport::new();
+        page(&transport, &[1, 2]);
+        page(&transport, &[3, 4]);
+
+        let mut paginator = Paginator::new(categories(&transport)).page_size(2);
+        for _ in 0..3 {
+            paginator.try_next().unwrap();
+        }
+        let cursor = paginator.cursor();
+        assert_eq!(cursor.start_index, 3);
+
+        let saved = serde_json::to_string(&cursor).unwrap();
+        let resumed_transport = MockTransport::new();
+        page(&resumed_transport, &[4]);
+        let resumed = Paginator::new(categories(&resumed_transport))
+            .page_size(2)
+            .resume(serde_json::from_str(&saved).unwrap());
+        assert_eq!(ids(resumed), vec![4]);
+        assert_eq!(pages_requested(&resumed_transport), vec![pair("3", "2")]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Client-side rate limiting, so that a busy program slows itself down rather than having Zoho
+//! refuse its requests. Zoho allows each user 100 requests in any two minute period, and locks
+//! out anyone exceeding that for a further thirty minutes.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// A quota of requests permitted over a period of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+}
+
+impl RateLimit {
+    /// Constructor for a RateLimit permitting the given number of requests per period.
+    /// No more than `requests` are made in any window of that length; once they have been,
+    /// the next waits until the oldest of them falls out of the window.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        RateLimit {
+            requests: requests.max(1),
+            per,
+        }
+    }
+
+    /// Zoho's documented quota, 100 requests per two minutes
+    pub fn zoho() -> Self {
+        RateLimit::new(100, Duration::from_secs(120))
+    }
+
+    /// Accessor method for the number of requests permitted per period
+    pub fn requests(&self) -> u32 {
+        self.requests
+    }
+
+    /// Accessor method for the length of the period
+    pub fn per(&self) -> Duration {
+        self.per
+    }
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit::zoho()
+    }
+}
+
+/// A sliding window enforcing a RateLimit. ZohoClient holds one behind an Arc, so that every
+/// clone of a client draws on the same quota.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    window: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    /// Constructor for a RateLimiter which has made no requests yet
+    pub fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            window: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Accessor method for the quota being enforced
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Take a request from the quota, blocking the current thread until one is available.
+    pub fn acquire(&self) {
+        let wait = self.reserve();
+        if self.must_wait(wait) {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Take a request from the quota, waiting without blocking the thread until one is
+    /// available. Needs a Tokio runtime.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self) {
+        let wait = self.reserve();
+        if self.must_wait(wait) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn must_wait(&self, wait: Duration) -> bool {
+        if wait == Duration::from_secs(0) {
+            return false;
+        }
+        info!(
+            "Zoho rate limit of {} requests per {:?} reached; waiting {:?}",
+            self.limit.requests, self.limit.per, wait
+        );
+        true
+    }
+
+    /// Take a request from the quota, returning how long the caller must wait before making it.
+    /// The request counts against the quota immediately, so callers which don't wait will
+    /// quickly exceed it.
+    pub fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    // Reserve a slot for a request to be sent no earlier than `now`. The window holds the send
+    // time of each recent request, including those promised a slot but still waiting for it,
+    // in order; each new slot is no earlier than the last, so waiting callers queue fairly.
+    fn reserve_at(&self, now: Instant) -> Duration {
+        // Nothing done while holding the lock can panic, so a poisoned window is still sound.
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+
+        while window
+            .front()
+            .is_some_and(|sent| *sent + self.limit.per <= now)
+        {
+            window.pop_front();
+        }
+
+        let mut slot = window.back().map_or(now, |last| (*last).max(now));
+        let requests = self.limit.requests as usize;
+        if window.len() >= requests {
+            // The request this one's slot must clear the window of.
+            slot = slot.max(window[window.len() - requests] + self.limit.per);
+        }
+        window.push_back(slot);
+
+        let wait = slot - now;
+        if wait > Duration::from_secs(0) {
+            debug!(
+                "{} requests queued for the Zoho rate limit",
+                window.iter().filter(|sent| **sent > now).count()
+            );
+        }
+        wait
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::new(RateLimit::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_within_the_quota_do_not_wait() {
+        let limiter = RateLimiter::new(RateLimit::new(3, Duration::from_secs(10)));
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve_at(now), Duration::from_secs(0));
+        }
+    }
+
+    #[test]
+    fn excess_requests_wait_for_the_oldest_to_leave_the_window() {
+        let limiter = RateLimiter::new(RateLimit::new(2, Duration::from_secs(10)));
+        let start = Instant::now();
+        limiter.reserve_at(start);
+        limiter.reserve_at(start + Duration::from_secs(4));
+
+        let now = start + Duration::from_secs(5);
+        assert_eq!(limiter.reserve_at(now), Duration::from_secs(5));
+        // Queued behind the last, and clearing the window of the second request.
+        assert_eq!(limiter.reserve_at(now), Duration::from_secs(9));
+    }
+
+    #[test]
+    fn window_slides_rather_than_refilling() {
+        let limiter = RateLimiter::new(RateLimit::new(2, Duration::from_secs(10)));
+        let start = Instant::now();
+        limiter.reserve_at(start);
+        limiter.reserve_at(start + Duration::from_secs(9));
+
+        // The first has left the window, but the second still counts against it.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.reserve_at(later), Duration::from_secs(0));
+        assert_eq!(limiter.reserve_at(later), Duration::from_secs(9));
+    }
+}
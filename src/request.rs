@@ -1,40 +1,75 @@
 //! Principally a wrapper module for Reqwest, this abstracts the details necessary for actually
 //! creating and making a request to the Zoho API.
 
+use crate::client::ZohoClient;
 use crate::errors::*;
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpRequest, HttpResponse, RequestBody, Upload};
 use reqwest::{Method, StatusCode};
 use serde::{self, Deserialize};
 use std::collections::HashMap;
+use std::thread;
+use tracing::warn;
 
-// TODO(Xymist): Split this; POST/PUT requests should carry data, GET and DELETE should not.
-pub(crate) struct ZohoRequest<T>
+/// A single request to the Zoho API. Reads, deletes and actions such as following a forum carry
+/// no payload; creates and updates carry one, of the type given for the operation.
+pub(crate) struct ZohoRequest<T = ()>
 where
     T: serde::Serialize + Clone,
 {
     method: Method,
     url: String,
     data: Option<T>,
-    access_token: String,
+    files: Vec<(String, Upload)>,
     params: Option<HashMap<String, String>>,
 }
 
-impl<T: serde::Serialize + Clone> ZohoRequest<T> {
-    pub(crate) fn new(
-        method: Method,
-        url: &str,
-        data: Option<T>,
-        access_token: String,
-        params: Option<HashMap<String, String>>,
-    ) -> Self {
+impl ZohoRequest {
+    /// A request with no payload, e.g. to trigger an action on a record
+    pub(crate) fn new(method: Method, url: &str, params: Option<HashMap<String, String>>) -> Self {
         ZohoRequest {
             method,
             url: url.to_owned(),
-            data,
-            access_token,
+            data: None,
+            files: Vec::new(),
+            params,
+        }
+    }
+
+    /// A request to read the record or records at the URL
+    pub(crate) fn read(url: &str, params: Option<HashMap<String, String>>) -> Self {
+        ZohoRequest::new(Method::GET, url, params)
+    }
+
+    /// A request to delete the record at the URL
+    pub(crate) fn delete(url: &str, params: Option<HashMap<String, String>>) -> Self {
+        ZohoRequest::new(Method::DELETE, url, params)
+    }
+}
+
+impl<T: serde::Serialize + Clone> ZohoRequest<T> {
+    /// A request sending data to the URL: to a collection's URL to create a record there, or
+    /// to a record's own URL to update whichever of its fields are set in the data.
+    /// Zoho's REST API takes updates as a POST to the record's own URL rather than a PUT;
+    /// what distinguishes an update from a create is the URL and the partial payload.
+    pub(crate) fn write(url: &str, data: T, params: Option<HashMap<String, String>>) -> Self {
+        ZohoRequest {
+            method: Method::POST,
+            url: url.to_owned(),
+            data: Some(data),
+            files: Vec::new(),
             params,
         }
     }
 
+    /// Attach files to send alongside the data, each as the given field. This switches the
+    /// body from a form to multipart.
+    pub(crate) fn with_files(mut self, field: &str, files: &[Upload]) -> Self {
+        self.files
+            .extend(files.iter().map(|file| (field.to_owned(), file.clone())));
+        self
+    }
+
     pub(crate) fn method(&self) -> Method {
         self.method.clone()
     }
@@ -43,46 +78,132 @@ impl<T: serde::Serialize + Clone> ZohoRequest<T> {
         self.url.clone()
     }
 
-    fn access_token(&self) -> String {
-        self.access_token.clone()
+    /// Convert this ZohoRequest into an HttpRequest ready to be passed to a Transport.
+    pub(crate) fn http_request(&self, access_token: &str) -> Result<HttpRequest> {
+        let mut request = HttpRequest::new(self.method(), &self.url())
+            .header("Authorization", &format!("Bearer {}", access_token))?;
+        if let Some(ref params) = self.params {
+            request = request.query(params.clone());
+        }
+        if let Some(ref data) = self.data {
+            let fields = url_pairs(data)?;
+            request = request.body(if self.files.is_empty() {
+                RequestBody::Form(fields)
+            } else {
+                RequestBody::multipart(fields, self.files.clone())
+            });
+        }
+        Ok(request)
     }
 
     /// The .send<U> method uses the details provided to the ZohoRequest to make a request
-    /// against the Zoho Projects API. This is generic over the various ZohoModels.
-    pub(crate) fn send<U>(&self) -> Result<Option<U>>
+    /// against the Zoho Projects API, through the Transport held by the given client.
+    /// This is generic over the various ZohoModels.
+    pub(crate) fn send<U>(&self, client: &ZohoClient) -> Result<U>
     where
-        U: serde::de::DeserializeOwned,
+        U: serde::de::DeserializeOwned + Default,
     {
-        let req_client = reqwest::blocking::Client::new();
-        let mut builder = req_client.request(self.method(), self.url());
-        builder = builder.header("Authorization", format!("Bearer {}", self.access_token()));
-        if let Some(ref params) = self.params {
-            builder = builder.query(params);
-        }
-        if let Some(ref data) = self.data {
-            builder = builder.query(data);
-        }
+        let policy = client.retry_policy();
+        let mut attempts = 0;
+        let response = loop {
+            // Rebuilt for each attempt, as a long wait may outlast the access token.
+            let request = self.http_request(&client.access_token()?)?;
+            client.rate_limiter().acquire();
+            attempts += 1;
+
+            match self.assess(&policy, attempts, client.transport().send(&request))? {
+                Attempt::Done(response) => break response,
+                Attempt::Retry(wait) => thread::sleep(wait),
+            }
+        };
 
-        let response = builder.send()?;
-        if !response.status().is_success() {
-            return Err(Error::server_error(response.status().to_string()));
+        self.parse(response)
+    }
+
+    /// Decide what to do with the outcome of an attempt at sending this request: accept a
+    /// successful response, fail, or wait and try again.
+    pub(crate) fn assess(
+        &self,
+        policy: &RetryPolicy,
+        attempts: u32,
+        outcome: Result<HttpResponse>,
+    ) -> Result<Attempt> {
+        let wait = match policy.retry_wait(&self.method, attempts, &outcome) {
+            Some(wait) => wait,
+            None => {
+                return match outcome {
+                    Ok(response) if response.status().is_success() => Ok(Attempt::Done(response)),
+                    Ok(response) => Err(self.api_error(&response)),
+                    Err(err) => Err(err),
+                }
+            }
         };
 
-        // TODO(Xymist): This should probably be an enum for types of response, rather than just an Option
-        if self.method() == Method::DELETE {
-            let _res_obj: DeleteResponse = match response.status() {
-                StatusCode::NO_CONTENT => return Err(Error::no_content()),
-                _ => response.json()?,
-            };
-            Ok(None)
-        } else {
-            let res_obj: U = match response.status() {
-                StatusCode::NO_CONTENT => return Err(Error::no_content()),
-                _ => response.json()?,
-            };
-            Ok(Some(res_obj))
+        let failure = match outcome {
+            Ok(response) => response.status().to_string(),
+            Err(err) => err.to_string(),
+        };
+        warn!(
+            "{} {} failed on attempt {} ({}); retrying in {:?}",
+            self.method, self.url, attempts, failure, wait
+        );
+        Ok(Attempt::Retry(wait))
+    }
+
+    /// Deserialize a successful response into the type the caller expects.
+    pub(crate) fn parse<U>(&self, response: HttpResponse) -> Result<U>
+    where
+        U: serde::de::DeserializeOwned + Default,
+    {
+        // Zoho answers a request matching nothing with 204 No Content rather than an empty list.
+        if response.status() == StatusCode::NO_CONTENT || response.body.is_empty() {
+            return Ok(U::default());
         }
+        response.json()
     }
+
+    // Zoho explains a rejection with a body like `{"error": {"code": 6404, "message": "..."}}`,
+    // though the code sometimes arrives as a string and gateways may send no JSON at all.
+    fn api_error(&self, response: &HttpResponse) -> Error {
+        let detail = response
+            .json::<serde_json::Value>()
+            .ok()
+            .and_then(|body| body.get("error").cloned());
+        let code = detail.as_ref().and_then(|d| d.get("code")).and_then(|c| {
+            c.as_i64()
+                .or_else(|| c.as_str().and_then(|s| s.trim().parse().ok()))
+        });
+        let message = detail
+            .as_ref()
+            .and_then(|d| d.get("message"))
+            .and_then(|m| m.as_str())
+            .map(str::to_owned)
+            .unwrap_or_else(|| {
+                response
+                    .status()
+                    .canonical_reason()
+                    .unwrap_or("unknown error")
+                    .to_owned()
+            });
+
+        Error::api(response.status(), code, &message, &self.url, &self.method)
+    }
+}
+
+/// The outcome of a single attempt at sending a ZohoRequest
+pub(crate) enum Attempt {
+    /// A successful response
+    Done(HttpResponse),
+    /// A failure worth retrying, after waiting this long
+    Retry(std::time::Duration),
+}
+
+// Flatten a serializable struct into key=value pairs, in the same form Zoho accepts as form fields.
+fn url_pairs(data: &impl serde::Serialize) -> Result<Vec<(String, String)>> {
+    let encoded = serde_urlencoded::to_string(data)?;
+    Ok(url::form_urlencoded::parse(encoded.as_bytes())
+        .into_owned()
+        .collect())
 }
 
 /// Wrapper for the details used when making a single request to the Zoho API
@@ -94,20 +215,20 @@ pub struct RequestDetails {
     pub id: Option<i64>,
     /// TODO(Xymist): Fix this
     pub name: Option<String>,
-    /// Token identifying the caller of the API.
-    pub access_token: String,
+    /// Client through which the request will be sent, carrying the caller's credentials.
+    pub client: ZohoClient,
     /// Any parameters to be passed in the request, such as filters for retrieval or fields for creation.
     pub params: HashMap<String, String>,
 }
 
 impl RequestDetails {
     /// Constructor method for new RequestDetails structs
-    pub fn new(access_token: &str, model_path: &str, id: Option<i64>) -> Self {
+    pub fn new(client: &ZohoClient, model_path: &str, id: Option<i64>) -> Self {
         RequestDetails {
             model_path: model_path.to_owned(),
             id,
             name: None,
-            access_token: access_token.to_owned(),
+            client: client.clone(),
             params: HashMap::new(),
         }
     }
@@ -121,7 +242,7 @@ impl RequestDetails {
 
     /// Constructor for URI string, to be used for making requests to the Zoho Projects API
     pub fn uri(&self) -> String {
-        let base_url = "https://projectsapi.zoho.com/restapi";
+        let base_url = self.client.api_base_url();
 
         match self.id {
             Some(model_id) => format!("{}/{}{}/", base_url, self.model_path, model_id),
@@ -129,14 +250,19 @@ impl RequestDetails {
         }
     }
 
+    /// Accessor method for the ID of the record referenced, if any
+    pub fn id(&self) -> Option<i64> {
+        self.id
+    }
+
     /// Accessor method for parameter hash
     pub fn params(&self) -> Option<HashMap<String, String>> {
         Some(self.params.clone())
     }
 
-    /// Accessor method for access token
-    pub fn access_token(&self) -> String {
-        self.access_token.clone()
+    /// Accessor method for the client
+    pub fn client(&self) -> &ZohoClient {
+        &self.client
     }
 }
 
@@ -151,85 +277,332 @@ pub trait FilterOptions {
 pub trait ModelRequest {
     /// Find or create the URI to call for this model
     fn uri(&self) -> String;
+    /// The ID of the single record this request refers to, if any
+    fn id(&self) -> Option<i64>;
     /// Set parameters to send with this request, in the standard `&key=value` format
     fn params(&self) -> Option<HashMap<String, String>>;
-    /// Find or create the access token with which we can authenticate the request
-    fn access_token(&self) -> String;
+    /// The client through which this request will be sent
+    fn client(&self) -> &ZohoClient;
     /// For GET requests, set filters to reduce the number of hits returned
-    fn filter(self, param: (impl FilterOptions + std::fmt::Display)) -> Self;
+    fn filter(self, param: impl FilterOptions + std::fmt::Display) -> Self;
 }
 
+/// A "ZohoModels" struct, in which the Zoho Projects API wraps every response about a model: a
+/// list of the model, even where only one was requested or created.
+pub trait Collection: serde::de::DeserializeOwned + Default {
+    /// The model this is a list of
+    type Model;
+
+    /// Unwrap the list of models
+    fn into_models(self) -> Vec<Self::Model>;
+}
+
+/// The single model returned by requests of type R, e.g. `Task` for a `TaskRequest`
+pub type ModelOf<R> = <<R as RequestParameters>::ModelCollection as Collection>::Model;
+
 /// Trait with global implementations for issuing requests of each Method.
-/// Implemented for each type of ModelRequest, overridden where a specific
+/// Implemented for each type of ModelRequest, with `permits` overridden where a specific
 /// request Method is not available for that model.
 pub trait RequestParameters: ModelRequest {
     /// ModelCollection must be a "ZohoModels" struct, which contains just Vec<ZohoModel>.
     /// The Zoho Projects API always returns an object containing a JSONArray of whatever model
     /// is being requested, even if requested by ID and therefore returning either a single item
     /// or an error.
-    type ModelCollection: serde::de::DeserializeOwned;
+    type ModelCollection: Collection;
 
     /// NewModel is a struct containing the fields which the Zoho API accepts to create a new item
     /// of the model type. For those models which Zoho does not accept creation events though the API
     /// this is an empty struct.
     type NewModel: serde::Serialize + Clone;
 
-    /// Send an HTTP GET request to the model
-    fn get(&self) -> Result<Option<Self::ModelCollection>> {
-        ZohoRequest::<Self::NewModel>::new(
-            Method::GET,
-            &self.uri(),
-            None,
-            self.access_token(),
-            self.params(),
-        )
-        .send()
+    /// UpdateModel is a struct containing the fields which the Zoho API accepts to change an
+    /// existing item of the model type, all optional; only those which are set are sent. For those
+    /// models which Zoho does not allow to be changed through the API this is an empty enum.
+    type UpdateModel: serde::Serialize + Clone;
+
+    /// Check that Zoho accepts requests of this Method for the model, failing with
+    /// `Error::DisallowedRequestMethod` if not. Updates are checked as PUT. Overridden by
+    /// models which cannot be changed through the API.
+    fn permits(&self, _method: &Method) -> Result<()> {
+        Ok(())
     }
 
-    /// Send an HTTP POST request to the model
-    fn post(&self, data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        ZohoRequest::<Self::NewModel>::new(
-            Method::POST,
-            &self.uri(),
-            Some(data),
-            self.access_token(),
-            self.params(),
-        )
-        .send()
+    /// Files to attach when creating an item of the model, each sent as an `uploadfile` field.
+    /// Overridden by models which take attachments.
+    fn uploads(&self, _data: &Self::NewModel) -> Vec<Upload> {
+        Vec::new()
     }
 
-    /// Send an HTTP PUT request to the model
-    fn put(&self, data: Self::NewModel) -> Result<Option<Self::ModelCollection>> {
-        ZohoRequest::<Self::NewModel>::new(
-            Method::POST,
+    /// The ID of this item if it has records nested beneath it, to be listed after it when
+    /// paging through every record, e.g. a Task with subtasks. Overridden by models with
+    /// children.
+    fn parent_id(&self, _item: &ModelOf<Self>) -> Option<i64> {
+        None
+    }
+
+    /// A request for the records nested beneath the item with this ID, e.g. the subtasks of
+    /// a Task. Overridden by models with children.
+    fn nested(&self, _parent_id: i64) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Read the model, sending an HTTP GET request. Where nothing matches, the collection is empty.
+    fn get(&self) -> Result<Self::ModelCollection> {
+        self.permits(&Method::GET)?;
+        ZohoRequest::read(&self.uri(), self.params()).send(self.client())
+    }
+
+    /// Read the single item of the model with this request's ID, failing with
+    /// `Error::NotFound` if there is no such item. Fails with `Error::MissingRecordId`, without
+    /// sending anything, if the request has no ID.
+    fn get_one(&self) -> Result<ModelOf<Self>> {
+        targets_record(self, &Method::GET)?;
+        first(self.get()?, &self.uri())
+    }
+
+    /// Create a new item of the model, returning it as Zoho recorded it. Fails with
+    /// `Error::UnexpectedRecordId`, without sending anything, if the request has an ID.
+    fn post(&self, data: Self::NewModel) -> Result<ModelOf<Self>> {
+        self.permits(&Method::POST)?;
+        targets_collection(self, &Method::POST)?;
+        let files = self.uploads(&data);
+        first(
+            ZohoRequest::write(&self.uri(), data, self.params())
+                .with_files("uploadfile", &files)
+                .send::<Self::ModelCollection>(self.client())?,
             &self.uri(),
-            Some(data),
-            self.access_token(),
-            self.params(),
         )
-        .send()
     }
 
-    /// Send an HTTP DELETE request to the model
-    fn delete(&self) -> Result<Option<Self::ModelCollection>> {
-        ZohoRequest::<Self::NewModel>::new(
-            Method::DELETE,
+    /// Update the item of the model with this request's ID, changing only the fields set in
+    /// `data`, and return it as Zoho now has it. Fails with `Error::MissingRecordId`, without
+    /// sending anything, if the request has no ID.
+    fn put(&self, data: Self::UpdateModel) -> Result<ModelOf<Self>> {
+        self.permits(&Method::PUT)?;
+        targets_record(self, &Method::PUT)?;
+        first(
+            ZohoRequest::write(&self.uri(), data, self.params())
+                .send::<Self::ModelCollection>(self.client())?,
             &self.uri(),
-            None,
-            self.access_token(),
-            self.params(),
         )
-        .send()
+    }
+
+    /// Delete the item of the model with this request's ID, sending an HTTP DELETE request.
+    /// Fails with `Error::MissingRecordId`, without sending anything, if the request has no ID.
+    fn delete(&self) -> Result<Deleted> {
+        self.permits(&Method::DELETE)?;
+        targets_record(self, &Method::DELETE)?;
+        ZohoRequest::delete(&self.uri(), self.params()).send(self.client())
+    }
+}
+
+// Reading, updating or deleting one record needs its ID; without one the request would be
+// sent to the collection's URL, where Zoho would list every record, or take an update as a
+// request to create.
+pub(crate) fn targets_record(
+    request: &(impl ModelRequest + ?Sized),
+    method: &Method,
+) -> Result<()> {
+    match request.id() {
+        Some(_) => Ok(()),
+        None => Err(Error::missing_record_id(method.as_str(), &request.uri())),
+    }
+}
+
+// Creations apply to the collection; with an ID they would be sent to that record's URL,
+// where Zoho would take them as an update of it.
+pub(crate) fn targets_collection(
+    request: &(impl ModelRequest + ?Sized),
+    method: &Method,
+) -> Result<()> {
+    match request.id() {
+        Some(_) => Err(Error::unexpected_record_id(method.as_str(), &request.uri())),
+        None => Ok(()),
     }
 }
 
+// Take the one model from a response which should contain exactly one.
+pub(crate) fn first<C: Collection>(collection: C, url: &str) -> Result<C::Model> {
+    collection
+        .into_models()
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::not_found(url))
+}
+
 /// On deleting an item the return value is an object with a response String in it, not the deleted item or
-/// an empty list as might usually be expected. This struct packages that so that there is a valid return
-/// type available for Delete responses to be parsed into.
-/// This type will never be returned; if a Delete request is successfully made the response
-/// from this wrapper is Ok(None).
-#[allow(dead_code)]
-#[derive(Deserialize, Clone)]
-struct DeleteResponse {
-    response: String,
+/// an empty list as might usually be expected. This struct packages that message, typically a
+/// confirmation such as "Task deleted successfully".
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Deleted {
+    /// Zoho's description of the deletion
+    pub response: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://projectsapi.zoho.com/restapi/portal/1/projects/";
+
+    fn failure(request: &ZohoRequest, status: StatusCode, body: &str) -> Error {
+        match request.assess(&RetryPolicy::none(), 1, Ok(HttpResponse::new(status, body))) {
+            Err(err) => err,
+            Ok(_) => panic!("a {} response should fail", status),
+        }
+    }
+
+    #[test]
+    fn assess_accepts_success() {
+        let request = ZohoRequest::read(URL, None);
+        let outcome = request.assess(
+            &RetryPolicy::new(),
+            1,
+            Ok(HttpResponse::new(StatusCode::OK, "{}")),
+        );
+        assert!(matches!(outcome, Ok(Attempt::Done(_))));
+    }
+
+    #[test]
+    fn assess_retries_server_errors_on_reads() {
+        let request = ZohoRequest::read(URL, None);
+        let outcome = request.assess(
+            &RetryPolicy::new(),
+            1,
+            Ok(HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")),
+        );
+        assert!(matches!(outcome, Ok(Attempt::Retry(_))));
+    }
+
+    #[test]
+    fn assess_does_not_retry_server_errors_on_creates() {
+        let request = ZohoRequest::write(URL, (), None);
+        let err = request
+            .assess(
+                &RetryPolicy::new(),
+                1,
+                Ok(HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")),
+            )
+            .err()
+            .expect("a POST should not be retried");
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn api_error_reads_zoho_error_body() {
+        let request = ZohoRequest::read(URL, None);
+        let err = failure(
+            &request,
+            StatusCode::BAD_REQUEST,
+            r#"{"error": {"code": 6831, "message": "Input Parameter Missing"}}"#,
+        );
+        match err {
+            Error::Api {
+                status,
+                code,
+                message,
+                url,
+                ..
+            } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(code, Some(6831));
+                assert_eq!(message, "Input Parameter Missing");
+                assert_eq!(url, URL);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn api_error_accepts_string_codes() {
+        let request = ZohoRequest::read(URL, None);
+        let err = failure(
+            &request,
+            StatusCode::NOT_FOUND,
+            r#"{"error": {"code": "6404", "message": "Resource not found"}}"#,
+        );
+        assert!(matches!(
+            err,
+            Error::Api {
+                code: Some(6404),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn api_error_without_json_uses_status() {
+        let request = ZohoRequest::read(URL, None);
+        let err = failure(
+            &request,
+            StatusCode::BAD_GATEWAY,
+            "<html>Bad Gateway</html>",
+        );
+        match err {
+            Error::Api { code, message, .. } => {
+                assert_eq!(code, None);
+                assert_eq!(message, "Bad Gateway");
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_treats_no_content_as_empty() {
+        let request = ZohoRequest::read(URL, None);
+        let parsed: Vec<i64> = request
+            .parse(HttpResponse::new(StatusCode::NO_CONTENT, ""))
+            .expect("empty result");
+        assert!(parsed.is_empty());
+    }
+
+    #[derive(Clone, serde::Serialize)]
+    struct Payload {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        owner: Option<i64>,
+    }
+
+    #[test]
+    fn write_sends_data_as_form_fields() {
+        let mut params = HashMap::new();
+        params.insert("sort".to_owned(), "asc".to_owned());
+        let payload = Payload {
+            name: "Fix & ship".to_owned(),
+            owner: None,
+        };
+        let request = ZohoRequest::write(URL, payload, Some(params))
+            .http_request("token")
+            .expect("valid request");
+
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.query_param("sort"), Some("asc"));
+        assert_eq!(
+            request.headers.get("Authorization").unwrap(),
+            "Bearer token"
+        );
+        let body = request.body.expect("a form body");
+        assert_eq!(body.field("name"), Some("Fix & ship"));
+        assert_eq!(body.field("owner"), None);
+        assert_eq!(body.to_bytes(), b"name=Fix+%26+ship");
+    }
+
+    #[test]
+    fn write_with_files_sends_multipart() {
+        let payload = Payload {
+            name: "Notes".to_owned(),
+            owner: Some(7),
+        };
+        let request = ZohoRequest::write(URL, payload, None)
+            .with_files("uploadfile", &[Upload::new("a.txt", "hello")])
+            .http_request("token")
+            .expect("valid request");
+
+        let body = request.body.expect("a multipart body");
+        assert!(body.content_type().starts_with("multipart/form-data"));
+        assert_eq!(body.field("owner"), Some("7"));
+    }
 }
@@ -0,0 +1,254 @@
+//! Retrying of requests which fail for reasons likely to pass, such as Zoho being briefly
+//! overloaded or a connection dropping.
+
+use crate::errors::*;
+use crate::transport::HttpResponse;
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// How ZohoClient retries requests which fail with a rate limiting (429) or server (5xx)
+/// status, or which time out or fail to connect. Waits between attempts grow exponentially,
+/// with jitter, unless Zoho says how long to wait with a `Retry-After` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Constructor for the default RetryPolicy: four attempts in all, waiting half a second
+    /// after the first failure and at most thirty seconds, and only for idempotent methods.
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// A RetryPolicy which makes every request exactly once
+    pub fn none() -> Self {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /// Make at most this many attempts at each request, including the first
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Wait around this long before the first retry, doubling for each one after
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Never wait longer than this between attempts, including when told to by `Retry-After`
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Retry POST requests too. A POST which reached Zoho before failing may be applied
+    /// twice, e.g. creating duplicate records, so this is off by default.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// Whether a request may be attempted again after the given number of attempts. One
+    /// which Zoho certainly didn't act on, e.g. because it was refused for rate limiting,
+    /// is safe to retry whatever its method.
+    pub(crate) fn allows(&self, method: &Method, attempts: u32, unprocessed: bool) -> bool {
+        attempts < self.max_attempts
+            && (unprocessed || self.retry_non_idempotent || is_idempotent(method))
+    }
+
+    /// How long to wait before attempting a request again after the given outcome of its
+    /// latest attempt, or None if it succeeded or shouldn't be retried.
+    pub(crate) fn retry_wait(
+        &self,
+        method: &Method,
+        attempts: u32,
+        outcome: &Result<HttpResponse>,
+    ) -> Option<Duration> {
+        match outcome {
+            Ok(response) if response.status().is_success() => None,
+            Ok(response) => {
+                let status = response.status();
+                let unprocessed = status == StatusCode::TOO_MANY_REQUESTS;
+                (retryable_status(status) && self.allows(method, attempts, unprocessed))
+                    .then(|| self.backoff(attempts, Some(response)))
+            }
+            Err(err) => {
+                let unprocessed = matches!(err, Error::Reqwest(ref e) if e.is_connect());
+                (retryable_error(err) && self.allows(method, attempts, unprocessed))
+                    .then(|| self.backoff(attempts, None))
+            }
+        }
+    }
+
+    /// How long to wait after the given number of failed attempts, preferring the
+    /// server's `Retry-After` if the response carried one.
+    pub(crate) fn backoff(&self, attempts: u32, response: Option<&HttpResponse>) -> Duration {
+        if let Some(retry_after) = response.and_then(retry_after) {
+            return retry_after.min(self.max_backoff);
+        }
+
+        let exponent = attempts.saturating_sub(1).min(16);
+        let ceiling = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        // Wait somewhere between half and all of the ceiling, so that clients which failed
+        // together don't all retry together.
+        ceiling / 2 + ceiling.mul_f64(rand::thread_rng().gen_range(0.0..0.5))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+/// Whether a response status indicates a failure worth retrying
+pub(crate) fn retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::INTERNAL_SERVER_ERROR
+        || status == StatusCode::BAD_GATEWAY
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || status == StatusCode::GATEWAY_TIMEOUT
+}
+
+/// Whether an error from a Transport indicates a failure worth retrying
+pub(crate) fn retryable_error(err: &Error) -> bool {
+    match err {
+        Error::Reqwest(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        _ => false,
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+// Retry-After is either a number of seconds or an HTTP date.
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    let value = response.headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_after(value: &str) -> HttpResponse {
+        let mut response = HttpResponse::new(StatusCode::TOO_MANY_REQUESTS, "");
+        response
+            .headers
+            .insert(RETRY_AFTER, value.parse().expect("valid header value"));
+        response
+    }
+
+    #[test]
+    fn retries_idempotent_methods_until_attempts_run_out() {
+        let policy = RetryPolicy::new().max_attempts(3);
+        assert!(policy.allows(&Method::GET, 1, false));
+        assert!(policy.allows(&Method::DELETE, 2, false));
+        assert!(!policy.allows(&Method::GET, 3, false));
+    }
+
+    #[test]
+    fn retries_post_only_if_unprocessed_or_allowed() {
+        let policy = RetryPolicy::new();
+        assert!(!policy.allows(&Method::POST, 1, false));
+        assert!(policy.allows(&Method::POST, 1, true));
+        assert!(policy
+            .retry_non_idempotent(true)
+            .allows(&Method::POST, 1, false));
+    }
+
+    #[test]
+    fn none_makes_a_single_attempt() {
+        assert!(!RetryPolicy::none().allows(&Method::GET, 1, true));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_within_jitter() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(60));
+        for (attempts, ceiling) in [(1, 1), (2, 2), (3, 4)] {
+            let ceiling = Duration::from_secs(ceiling);
+            let wait = policy.backoff(attempts, None);
+            assert!(wait >= ceiling / 2 && wait <= ceiling, "{:?}", wait);
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(5));
+        assert!(policy.backoff(10, None) <= Duration::from_secs(5));
+        assert_eq!(
+            policy.backoff(1, Some(&response_after("120"))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn retry_after_in_seconds() {
+        assert_eq!(
+            retry_after(&response_after(" 7 ")),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            RetryPolicy::new().backoff(1, Some(&response_after("7"))),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn retry_after_as_a_date() {
+        let later = chrono::Utc::now() + chrono::Duration::seconds(20);
+        let wait = retry_after(&response_after(&later.to_rfc2822())).expect("parsed date");
+        assert!(wait > Duration::from_secs(15) && wait <= Duration::from_secs(20));
+
+        let earlier = chrono::Utc::now() - chrono::Duration::seconds(20);
+        assert_eq!(retry_after(&response_after(&earlier.to_rfc2822())), None);
+    }
+
+    #[test]
+    fn retry_after_unparseable_falls_back() {
+        assert_eq!(retry_after(&response_after("soon")), None);
+        assert!(
+            RetryPolicy::new().backoff(1, Some(&response_after("soon"))) < Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!retryable_status(StatusCode::NOT_FOUND));
+        assert!(!retryable_status(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,791 @@
+//! Scopes bind requests to a Portal, or to a Project within one. Each model is provided its
+//! own plural-named method on the scope it belongs to for retrieving many entries.
+//! Where possible there is also a singular-names method which takes an ID parameter to retrieve a single entry.
+//! Some entities such as Activities are only available from the Zoho API as a list; in these cases a singular call has not been provided.
+//!
+//! Project-level requests exist only on ProjectScope, so a request made before choosing a
+//! Project fails to compile rather than at runtime.
+
+use crate::client::ZohoClient;
+use crate::errors::*;
+use crate::models::{
+    activity, bug, category, event, forum, milestone, portal, project, status, task, tasklist,
+    timesheet,
+};
+use crate::paginator::Paginator;
+use crate::request::{ModelOf, RequestParameters};
+
+/// A Portal or Project, identified either by its numeric ID or by name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ident {
+    /// The numeric ID, used without any lookup
+    Id(i64),
+    /// The name or `id_string`, looked up among those available. A name which matches no
+    /// record exactly may still match one ignoring case.
+    Name(String),
+}
+
+impl From<i64> for Ident {
+    fn from(id: i64) -> Self {
+        Ident::Id(id)
+    }
+}
+
+impl From<&str> for Ident {
+    fn from(name: &str) -> Self {
+        Ident::Name(name.to_owned())
+    }
+}
+
+impl From<String> for Ident {
+    fn from(name: String) -> Self {
+        Ident::Name(name)
+    }
+}
+
+/// Requests within a single Portal. Created with `ZohoClient::portal`.
+#[derive(Clone, Debug)]
+pub struct PortalScope {
+    client: ZohoClient,
+    portal_id: i64,
+}
+
+impl PortalScope {
+    pub(crate) fn new(client: &ZohoClient, portal_id: i64) -> Self {
+        PortalScope {
+            client: client.clone(),
+            portal_id,
+        }
+    }
+
+    /// Accessor method for the ID of this Portal
+    pub fn id(&self) -> i64 {
+        self.portal_id
+    }
+
+    /// Accessor method for the client through which requests are made
+    pub fn client(&self) -> &ZohoClient {
+        &self.client
+    }
+
+    /// Scope requests to a Project within this Portal, given its ID or name. An ID is used as
+    /// it is; a name is looked up among the Portal's Projects.
+    pub fn project(&self, project: impl Into<Ident>) -> Result<ProjectScope> {
+        let project_id = match project.into() {
+            Ident::Id(id) => id,
+            Ident::Name(name) => self.find_project(&name)?,
+        };
+        Ok(self.project_id(project_id))
+    }
+
+    pub(crate) fn project_id(&self, project_id: i64) -> ProjectScope {
+        ProjectScope {
+            client: self.client.clone(),
+            portal_id: self.portal_id,
+            project_id,
+        }
+    }
+
+    /// Run a request in every Project in this Portal, yielding each record tagged with the
+    /// Project it came from, e.g. `portal.for_each_project(|project| project.tasks())`.
+    /// The Projects are listed once, and each request paged through in turn. Every request
+    /// passes through the client's rate limiter, which clones share, so the whole run stays
+    /// within its quota.
+    ///
+    /// If a Project's records cannot be fetched, the error is yielded in their place and
+    /// iteration continues with the next Project.
+    pub fn for_each_project<R, F>(&self, request: F) -> AcrossProjects<R, F>
+    where
+        R: RequestParameters + Clone,
+        F: FnMut(&ProjectScope) -> R,
+    {
+        AcrossProjects::new(self, None, request)
+    }
+
+    /// Run a request in each of the given Projects, as `for_each_project` does. An ID which
+    /// is not among this Portal's Projects is yielded as an error once the rest are done.
+    pub fn for_projects<R, F>(
+        &self,
+        project_ids: impl IntoIterator<Item = i64>,
+        request: F,
+    ) -> AcrossProjects<R, F>
+    where
+        R: RequestParameters + Clone,
+        F: FnMut(&ProjectScope) -> R,
+    {
+        AcrossProjects::new(self, Some(project_ids.into_iter().collect()), request)
+    }
+
+    pub(crate) fn find_project(&self, project_name: &str) -> Result<i64> {
+        find_by_name("project", project_name, self.projects().iter_get())
+    }
+
+    /// Construct a Request for retrieving the users with access to this Portal
+    pub fn portal_users(&self) -> portal::user::PortalUserRequest {
+        portal::user::PortalUserRequest::new(
+            &self.client,
+            &portal::user::model_path(self.portal_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving the authorized user's Tasks across every Project
+    /// in this Portal
+    pub fn my_tasks(&self) -> task::MyTaskRequest {
+        task::MyTaskRequest::new(&self.client, &task::my_tasks_path(self.portal_id))
+    }
+
+    /// Construct a Request for retrieving the authorized user's Bugs across every Project
+    /// in this Portal
+    pub fn my_bugs(&self) -> bug::MyBugRequest {
+        bug::MyBugRequest::new(&self.client, &bug::my_bugs_path(self.portal_id))
+    }
+
+    /// Construct a Request for retrieving multiple Projects
+    pub fn projects(&self) -> project::ProjectRequest {
+        project::ProjectRequest::new(&self.client, &project::model_path(self.portal_id), None)
+    }
+
+    // The record of a single Project, exposed through ProjectScope::project
+    pub(crate) fn project_record(&self, id: i64) -> project::ProjectRequest {
+        project::ProjectRequest::new(&self.client, &project::model_path(self.portal_id), Some(id))
+    }
+}
+
+/// Requests within a single Project. Created with `PortalScope::project`, or
+/// `ZohoClient::project_scope` for the Project the client was built with.
+#[derive(Clone, Debug)]
+pub struct ProjectScope {
+    client: ZohoClient,
+    portal_id: i64,
+    project_id: i64,
+}
+
+impl ProjectScope {
+    /// Accessor method for the ID of this Project
+    pub fn id(&self) -> i64 {
+        self.project_id
+    }
+
+    /// The Portal this Project belongs to
+    pub fn portal(&self) -> PortalScope {
+        PortalScope::new(&self.client, self.portal_id)
+    }
+
+    /// Accessor method for the client through which requests are made
+    pub fn client(&self) -> &ZohoClient {
+        &self.client
+    }
+
+    /// Construct a Request for retrieving this Project
+    pub fn project(&self) -> project::ProjectRequest {
+        self.portal().project_record(self.project_id)
+    }
+
+    /// Construct a Request for retrieving multiple Activities. Activities cannot be
+    /// requested singly, therefore there is no id-parameterised counterpart.
+    pub fn activities(&self) -> activity::ActivityRequest {
+        activity::ActivityRequest::new(
+            &self.client,
+            &activity::model_path(self.portal_id, self.project_id),
+        )
+    }
+
+    /// Construct a Request for retrieving a Bug by numeric ID
+    pub fn bug(&self, id: i64) -> bug::BugRequest {
+        bug::BugRequest::new(
+            &self.client,
+            &bug::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Bugs
+    pub fn bugs(&self) -> bug::BugRequest {
+        bug::BugRequest::new(
+            &self.client,
+            &bug::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Category by ID
+    pub fn category(&self, id: i64) -> category::CategoryRequest {
+        category::CategoryRequest::new(
+            &self.client,
+            &category::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Categories
+    pub fn categories(&self) -> category::CategoryRequest {
+        category::CategoryRequest::new(
+            &self.client,
+            &category::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving an Event by ID
+    pub fn event(&self, id: i64) -> event::EventRequest {
+        event::EventRequest::new(
+            &self.client,
+            &event::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Events
+    pub fn events(&self) -> event::EventRequest {
+        event::EventRequest::new(
+            &self.client,
+            &event::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Forum by ID
+    pub fn forum(&self, id: i64) -> forum::ForumRequest {
+        forum::ForumRequest::new(
+            &self.client,
+            &forum::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Fora
+    pub fn forums(&self) -> forum::ForumRequest {
+        forum::ForumRequest::new(
+            &self.client,
+            &forum::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Comment within a Forum, referencing the
+    /// ID of both the Forum and Comment
+    pub fn forum_comment(&self, forum_id: i64, id: i64) -> forum::comment::CommentRequest {
+        forum::comment::CommentRequest::new(
+            &self.client,
+            &forum::comment::model_path(self.portal_id, self.project_id, forum_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Comments within a Forum, referencing
+    /// the Forum ID
+    pub fn forum_comments(&self, forum_id: i64) -> forum::comment::CommentRequest {
+        forum::comment::CommentRequest::new(
+            &self.client,
+            &forum::comment::model_path(self.portal_id, self.project_id, forum_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Milestone by ID
+    pub fn milestone(&self, id: i64) -> milestone::MilestoneRequest {
+        milestone::MilestoneRequest::new(
+            &self.client,
+            &milestone::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Milestones
+    pub fn milestones(&self) -> milestone::MilestoneRequest {
+        milestone::MilestoneRequest::new(
+            &self.client,
+            &milestone::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving the Users with access to this Project
+    pub fn project_users(&self) -> project::user::ProjectUserRequest {
+        project::user::ProjectUserRequest::new(
+            &self.client,
+            &project::user::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Statuses. There is no singular counterpart.
+    pub fn statuses(&self) -> status::StatusRequest {
+        status::StatusRequest::new(
+            &self.client,
+            &status::model_path(self.portal_id, self.project_id),
+        )
+    }
+
+    /// Construct a Request for retrieving a Task by ID
+    pub fn task(&self, id: i64) -> task::TaskRequest {
+        task::TaskRequest::new(
+            &self.client,
+            &task::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Tasks
+    pub fn tasks(&self) -> task::TaskRequest {
+        task::TaskRequest::new(
+            &self.client,
+            &task::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Tasklist by ID
+    pub fn tasklist(&self, id: i64) -> tasklist::TasklistRequest {
+        tasklist::TasklistRequest::new(
+            &self.client,
+            &tasklist::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Tasklists
+    pub fn tasklists(&self) -> tasklist::TasklistRequest {
+        tasklist::TasklistRequest::new(
+            &self.client,
+            &tasklist::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Task belonging to a specific Tasklist
+    pub fn tasklist_task(
+        &self,
+        tasklist_id: usize,
+        id: i64,
+    ) -> tasklist::task::TasklistTaskRequest {
+        tasklist::task::TasklistTaskRequest::new(
+            &self.client,
+            &tasklist::task::model_path(self.portal_id, self.project_id, tasklist_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving Tasks belonging to a specific Tasklist
+    pub fn tasklist_tasks(&self, tasklist_id: usize) -> tasklist::task::TasklistTaskRequest {
+        tasklist::task::TasklistTaskRequest::new(
+            &self.client,
+            &tasklist::task::model_path(self.portal_id, self.project_id, tasklist_id),
+            None,
+        )
+    }
+
+    /// Construct a Request for retrieving a Timesheet by ID
+    pub fn timesheet(&self, id: i64) -> timesheet::TimesheetRequest {
+        timesheet::TimesheetRequest::new(
+            &self.client,
+            &timesheet::model_path(self.portal_id, self.project_id),
+            Some(id),
+        )
+    }
+
+    /// Construct a Request for retrieving multiple Timesheets
+    pub fn timesheets(&self) -> timesheet::TimesheetRequest {
+        timesheet::TimesheetRequest::new(
+            &self.client,
+            &timesheet::model_path(self.portal_id, self.project_id),
+            None,
+        )
+    }
+}
+
+/// A record fetched by one of PortalScope's multi-project helpers, along with the Project
+/// it belongs to.
+#[derive(Clone, Debug)]
+pub struct InProject<T> {
+    /// ID of the Project the record belongs to
+    pub project_id: i64,
+    /// Name of the Project the record belongs to
+    pub project_name: String,
+    /// The record itself
+    pub item: T,
+}
+
+/// Iterator over the records a request returns in each of several Projects. Created with
+/// `PortalScope::for_each_project` or `PortalScope::for_projects`.
+pub struct AcrossProjects<R, F>
+where
+    R: RequestParameters + Clone,
+    F: FnMut(&ProjectScope) -> R,
+{
+    portal: PortalScope,
+    projects: Paginator<project::ProjectRequest>,
+    wanted: Option<Vec<i64>>,
+    request: F,
+    current: Option<(i64, String, Paginator<R>)>,
+    listed: bool,
+}
+
+impl<R, F> AcrossProjects<R, F>
+where
+    R: RequestParameters + Clone,
+    F: FnMut(&ProjectScope) -> R,
+{
+    fn new(portal: &PortalScope, wanted: Option<Vec<i64>>, request: F) -> Self {
+        AcrossProjects {
+            portal: portal.clone(),
+            projects: portal.projects().iter_get(),
+            wanted,
+            request,
+            current: None,
+            listed: false,
+        }
+    }
+
+    /// Fetch every remaining record, failing if any Project's records cannot be fetched
+    pub fn try_collect_all(self) -> Result<Vec<InProject<ModelOf<R>>>> {
+        self.collect()
+    }
+
+    // The next Project whose records are wanted, then an error for each wanted ID never
+    // listed, then None.
+    fn next_project(&mut self) -> Option<Result<project::Project>> {
+        if self.wanted.as_ref().is_some_and(|wanted| wanted.is_empty()) {
+            self.listed = true;
+        }
+
+        while !self.listed {
+            match self.projects.next() {
+                Some(Ok(project)) => match self.wanted {
+                    Some(ref mut wanted) => {
+                        if let Some(pos) = wanted.iter().position(|id| *id == project.id) {
+                            wanted.remove(pos);
+                            return Some(Ok(project));
+                        }
+                    }
+                    None => return Some(Ok(project)),
+                },
+                Some(Err(err)) => {
+                    // Without the listing there is no telling which IDs are missing.
+                    self.listed = true;
+                    self.wanted = None;
+                    return Some(Err(err));
+                }
+                None => self.listed = true,
+            }
+        }
+
+        match self.wanted {
+            Some(ref mut wanted) if !wanted.is_empty() => {
+                Some(Err(Error::missing_entity_id(wanted.remove(0))))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<R, F> Iterator for AcrossProjects<R, F>
+where
+    R: RequestParameters + Clone,
+    F: FnMut(&ProjectScope) -> R,
+{
+    type Item = Result<InProject<ModelOf<R>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((project_id, ref project_name, ref mut pages)) = self.current {
+                match pages.next() {
+                    Some(Ok(item)) => {
+                        return Some(Ok(InProject {
+                            project_id,
+                            project_name: project_name.clone(),
+                            item,
+                        }))
+                    }
+                    Some(Err(err)) => {
+                        self.current = None;
+                        return Some(Err(err));
+                    }
+                    None => self.current = None,
+                }
+            }
+
+            match self.next_project()? {
+                Ok(project) => {
+                    let scope = self.portal.project_id(project.id);
+                    let pages = Paginator::new((self.request)(&scope));
+                    self.current = Some((project.id, project.name, pages));
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+// The fields by which a Portal or Project may be looked up.
+pub(crate) trait Named {
+    fn id(&self) -> i64;
+    fn id_string(&self) -> &str;
+    fn name(&self) -> &str;
+}
+
+impl Named for portal::Portal {
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    fn id_string(&self) -> &str {
+        &self.id_string
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Named for project::Project {
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    fn id_string(&self) -> &str {
+        &self.id_string
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// The ID of the one record whose name or id_string is exactly `name`, or failing that whose
+// name matches it ignoring case. Every record is examined, so that a name shared by several
+// is reported rather than resolved arbitrarily.
+pub(crate) fn find_by_name<T: Named>(
+    model: &str,
+    name: &str,
+    records: impl IntoIterator<Item = Result<T>>,
+) -> Result<i64> {
+    let folded_name = name.to_lowercase();
+    let mut seen = 0;
+    let mut exact = Vec::new();
+    let mut folded = Vec::new();
+
+    for record in records {
+        let record = record?;
+        seen += 1;
+        if record.name() == name || record.id_string() == name {
+            exact.push((record.id(), record.name().to_owned()));
+        } else if record.name().to_lowercase() == folded_name {
+            folded.push((record.id(), record.name().to_owned()));
+        }
+    }
+
+    let matches = if exact.is_empty() { folded } else { exact };
+    match matches.as_slice() {
+        [] if seen == 0 => Err(Error::empty_entity_list(model)),
+        [] => Err(Error::missing_entity_name(name)),
+        [(id, _)] => Ok(*id),
+        _ => Err(Error::ambiguous_entity_name(
+            name,
+            matches
+                .iter()
+                .map(|(id, name)| format!("{} ({})", name, id))
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ModelRequest;
+    use crate::testing::{bug_json, project_json, task_json};
+    use crate::transport::MockTransport;
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    struct Record(i64, &'static str, &'static str);
+
+    impl Named for Record {
+        fn id(&self) -> i64 {
+            self.0
+        }
+
+        fn id_string(&self) -> &str {
+            self.1
+        }
+
+        fn name(&self) -> &str {
+            self.2
+        }
+    }
+
+    fn find(name: &str, records: Vec<Record>) -> Result<i64> {
+        find_by_name("projects", name, records.into_iter().map(Ok))
+    }
+
+    #[test]
+    fn matches_name_ignoring_case() {
+        let records = vec![Record(1, "1", "Website"), Record(2, "2", "Mobile App")];
+        assert_eq!(find("mobile app", records).unwrap(), 2);
+    }
+
+    #[test]
+    fn matches_id_string() {
+        let records = vec![Record(1, "acme", "Acme Ltd"), Record(2, "other", "Other")];
+        assert_eq!(find("acme", records).unwrap(), 1);
+    }
+
+    #[test]
+    fn exact_match_wins_over_case_folded() {
+        let records = vec![Record(1, "1", "website"), Record(2, "2", "Website")];
+        assert_eq!(find("Website", records).unwrap(), 2);
+    }
+
+    #[test]
+    fn shared_names_are_ambiguous() {
+        let records = vec![
+            Record(1, "1", "website"),
+            Record(2, "2", "WEBSITE"),
+            Record(3, "3", "Intranet"),
+        ];
+        match find("Website", records) {
+            Err(Error::AmbiguousEntityName { name, candidates }) => {
+                assert_eq!(name, "Website");
+                assert_eq!(candidates, vec!["website (1)", "WEBSITE (2)"]);
+            }
+            other => panic!("expected an ambiguous name, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn missing_and_empty() {
+        let records = vec![Record(1, "1", "Website")];
+        assert!(matches!(
+            find("Intranet", records),
+            Err(Error::MissingEntityName(_))
+        ));
+        assert!(matches!(
+            find("Intranet", Vec::new()),
+            Err(Error::EmptyList(_))
+        ));
+    }
+
+    #[test]
+    fn errors_fetching_records_are_returned() {
+        let records = vec![Ok(Record(1, "1", "Website")), Err(Error::transport("down"))];
+        assert!(find_by_name("projects", "Website", records).is_err());
+    }
+
+    #[test]
+    fn missing_scopes_are_errors() {
+        let client = ZohoClient::with_access_token("token", MockTransport::new());
+        assert!(matches!(client.portal_scope(), Err(Error::NoScope(ref s)) if s == "portal"));
+
+        let client = client.set_portal_id(3);
+        assert!(matches!(client.project_scope(), Err(Error::NoScope(ref s)) if s == "project"));
+        assert_eq!(client.portal_scope().unwrap().id(), 3);
+    }
+
+    #[test]
+    fn requests_are_made_beneath_their_scope() {
+        let client = ZohoClient::with_access_token("token", MockTransport::new())
+            .set_portal_id(3)
+            .set_project_id(7);
+        let project = client.project_scope().unwrap();
+
+        assert!(project
+            .tasks()
+            .uri()
+            .ends_with("/portal/3/projects/7/tasks/"));
+        assert!(project
+            .bug(5)
+            .uri()
+            .ends_with("/portal/3/projects/7/bugs/5/"));
+        let portal = client.portal(3).unwrap();
+        assert!(portal.projects().uri().ends_with("/portal/3/projects/"));
+        assert_eq!(portal.project(7).unwrap().id(), 7);
+    }
+
+    #[test]
+    fn project_names_are_looked_up() {
+        let transport = MockTransport::new();
+        let projects = json!({
+            "projects": [project_json(7, "Website"), project_json(8, "Intranet")]
+        });
+        transport.respond(StatusCode::OK, &projects.to_string());
+        transport.respond(StatusCode::OK, &projects.to_string());
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        let portal = client.portal(3).unwrap();
+
+        assert_eq!(portal.project("intranet").unwrap().id(), 8);
+        assert!(matches!(
+            portal.project("Mobile App"),
+            Err(Error::MissingEntityName(_))
+        ));
+        assert!(transport.requests()[0].url.ends_with("/portal/3/projects/"));
+    }
+
+    // A record as listed portal-wide, carrying the Project it belongs to.
+    fn in_project(mut record: serde_json::Value, project_id: i64) -> serde_json::Value {
+        record["project"] = json!({ "id": project_id, "name": format!("Project {}", project_id) });
+        record
+    }
+
+    #[test]
+    fn my_tasks_carry_their_project() {
+        let transport = MockTransport::new();
+        let tasks = json!({ "tasks": [in_project(task_json(1, false), 7)] });
+        transport.respond(StatusCode::OK, &tasks.to_string());
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        let portal = PortalScope::new(&client, 3);
+
+        let tasks = portal.my_tasks().iter_get().try_collect_all().unwrap();
+        let project = tasks[0].project.as_ref().expect("the owning project");
+        assert_eq!((project.id, project.name.as_str()), (7, "Project 7"));
+        assert!(transport.requests()[0].url.ends_with("/portal/3/mytasks/"));
+
+        let created = portal
+            .my_tasks()
+            .post(task::NewTask::builder("Launch").build());
+        assert!(matches!(
+            created,
+            Err(Error::DisallowedRequestMethod { .. })
+        ));
+    }
+
+    #[test]
+    fn my_bugs_carry_their_project() {
+        let transport = MockTransport::new();
+        let bugs = json!({ "bugs": [in_project(bug_json(9), 8)] });
+        transport.respond(StatusCode::OK, &bugs.to_string());
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        let portal = PortalScope::new(&client, 3);
+
+        let bugs = portal.my_bugs().iter_get().try_collect_all().unwrap();
+        assert_eq!(bugs[0].id, 9);
+        assert_eq!(bugs[0].project.as_ref().map(|project| project.id), Some(8));
+        assert!(transport.requests()[0].url.ends_with("/portal/3/mybugs/"));
+    }
+
+    #[test]
+    fn subtasks_are_listed_in_each_project() {
+        let transport = MockTransport::new();
+        let projects = json!({ "projects": [project_json(7, "Website")] });
+        transport.respond(StatusCode::OK, &projects.to_string());
+        let tasks = json!({ "tasks": [task_json(1, true)] });
+        transport.respond(StatusCode::OK, &tasks.to_string());
+        let subtasks = json!({ "tasks": [task_json(11, false)] });
+        transport.respond(StatusCode::OK, &subtasks.to_string());
+
+        let client = ZohoClient::with_access_token("token", transport.clone());
+        let portal = PortalScope::new(&client, 3);
+        let found: Vec<(i64, i64)> = portal
+            .for_each_project(|project| project.tasks().with_subtasks())
+            .map(|task| task.map(|task| (task.project_id, task.item.id)))
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(found, vec![(7, 1), (7, 11)]);
+        let requests = transport.requests();
+        assert!(requests[2]
+            .url
+            .contains("portal/3/projects/7/tasks/1/subtasks/"));
+    }
+}
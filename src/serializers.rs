@@ -1,5 +1,6 @@
+use chrono::NaiveDate;
 use serde::de::{self, Deserialize, Deserializer};
-use serde::ser::Serializer;
+use serde::ser::{self, Serialize, Serializer};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -26,3 +27,26 @@ where
         None => "".to_owned(),
     })
 }
+
+pub(crate) fn zoho_date<S>(maybe_date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match maybe_date {
+        Some(date) => serializer.serialize_str(&date.format("%m-%d-%Y").to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn json_string<S, T>(maybe_value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    match maybe_value {
+        Some(value) => {
+            serializer.serialize_str(&serde_json::to_string(value).map_err(ser::Error::custom)?)
+        }
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,67 @@
+//! Records in the shape Zoho returns them, for tests which need whole responses.
+
+use serde_json::{json, Value};
+
+// A Task with only the fields Zoho always returns, listing its subtasks if it has any.
+pub(crate) fn task_json(id: i64, subtasks: bool) -> Value {
+    json!({
+        "id": id,
+        "key": format!("T-{}", id),
+        "name": format!("Task {}", id),
+        "completed": false,
+        "last_updated_time": "03-09-2024",
+        "last_updated_time_long": 0,
+        "created_by": "Someone",
+        "created_person": "Someone",
+        "priority": "None",
+        "percent_complete": "0",
+        "details": { "owners": [] },
+        "link": { "self": { "url": "" }, "timesheet": { "url": "" } },
+        "status": { "name": "Open", "id": "1", "type": "open", "color_code": "" },
+        "subtasks": subtasks,
+    })
+}
+
+// A Project with only the fields Zoho always returns.
+pub(crate) fn project_json(id: i64, name: &str) -> Value {
+    json!({
+        "id": id,
+        "id_string": id.to_string(),
+        "name": name,
+        "created_date": "03-09-2024",
+        "created_date_format": "03-09-2024 09:00 AM",
+        "created_date_long": 0,
+        "IS_BUG_ENABLED": false,
+        "owner_name": "Someone",
+        "owner_id": "1",
+        "task_count": { "open": 0, "closed": 0 },
+        "milestone_count": { "open": 0, "closed": 0 },
+        "status": "active",
+        "link": {},
+        "workspace_id": "1",
+        "role": "admin",
+    })
+}
+
+// A Bug with only the fields Zoho always returns.
+pub(crate) fn bug_json(id: i64) -> Value {
+    json!({
+        "id": id,
+        "key": format!("B-{}", id),
+        "title": format!("Bug {}", id),
+        "module": { "id": 1, "name": "None" },
+        "status": { "id": "1", "type": "Open" },
+        "reproducible": { "id": 1, "type": "Always" },
+        "severity": { "id": 1, "type": "Minor" },
+        "classification": { "id": 1, "type": "Bug" },
+        "link": { "self": { "url": "" }, "timesheet": { "url": "" } },
+        "reported_person": "Someone",
+        "reporter_id": "1",
+        "assignee_name": "Someone",
+        "flag": "Internal",
+        "closed": false,
+        "created_time": "03-09-2024",
+        "created_time_format": "03-09-2024 09:00 AM",
+        "created_time_long": 0,
+    })
+}
@@ -0,0 +1,216 @@
+//! Persistence for OAuth tokens, so that one authorization can be reused across runs of a
+//! program rather than sending the user through the browser flow every time it starts.
+
+use crate::errors::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The token details worth keeping between runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredTokens {
+    /// Most recently issued access token, if any
+    pub access_token: Option<String>,
+    /// When the access token stops being valid
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Long-lived token used to obtain new access tokens without user interaction
+    pub refresh_token: Option<String>,
+    /// API domain Zoho reported for the account, which identifies its data centre
+    pub api_domain: Option<String>,
+}
+
+/// Somewhere OAuth tokens can be kept between runs. ZohoClient loads from its store on
+/// construction and saves to it whenever a token is issued or renewed.
+pub trait TokenStore: std::fmt::Debug + Send + Sync {
+    /// Fetch previously saved tokens, or None if nothing has been saved yet
+    fn load(&self) -> Result<Option<StoredTokens>>;
+    /// Save tokens, replacing anything previously stored
+    fn save(&self, tokens: &StoredTokens) -> Result<()>;
+    /// Discard anything stored, e.g. once the tokens have been revoked. By default this saves
+    /// an empty set of tokens over the old ones.
+    fn clear(&self) -> Result<()> {
+        self.save(&StoredTokens::default())
+    }
+}
+
+/// A TokenStore which keeps tokens as JSON in a file readable only by its owner.
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Constructor for a FileTokenStore at the given path. The file need not exist yet.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        FileTokenStore {
+            path: path.as_ref().to_owned(),
+        }
+    }
+
+    /// Accessor method for the path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        match fs::read(&self.path) {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        // Write alongside and then rename, so a crash mid-write can't leave a truncated file
+        // in place of a working refresh token.
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = private_file(&temp_path)?;
+        file.write_all(&serde_json::to_vec_pretty(tokens)?)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => Ok(result?),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn private_file(path: &Path) -> Result<fs::File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies on creation; tighten a file left over from an earlier run too.
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn private_file(path: &Path) -> Result<fs::File> {
+    Ok(fs::File::create(path)?)
+}
+
+/// A TokenStore which holds tokens in memory only. Clones share the same storage, so a handle
+/// kept outside the ZohoClient sees every token the client saves.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: Arc<Mutex<Option<StoredTokens>>>,
+}
+
+impl MemoryTokenStore {
+    /// Constructor for an empty MemoryTokenStore
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructor for a MemoryTokenStore already holding the given tokens
+    pub fn with_tokens(tokens: StoredTokens) -> Self {
+        MemoryTokenStore {
+            tokens: Arc::new(Mutex::new(Some(tokens))),
+        }
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone())
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        *self.tokens.lock().unwrap_or_else(|e| e.into_inner()) = Some(tokens.clone());
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        *self.tokens.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A path in the temporary directory unique to this test run.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "zohohorrorshow-{}-{}.json",
+            std::process::id(),
+            name
+        ))
+    }
+
+    fn tokens() -> StoredTokens {
+        StoredTokens {
+            access_token: Some("access".to_owned()),
+            expires_at: Some("2024-03-09T12:00:00Z".parse().unwrap()),
+            refresh_token: Some("refresh".to_owned()),
+            api_domain: Some("https://www.zohoapis.eu".to_owned()),
+        }
+    }
+
+    #[test]
+    fn file_store_round_trip() {
+        let store = FileTokenStore::new(temp_path("round-trip"));
+        assert_eq!(store.load().unwrap(), None);
+
+        store.save(&tokens()).unwrap();
+        assert_eq!(store.load().unwrap(), Some(tokens()));
+
+        store.clear().unwrap();
+        assert!(!store.path().exists());
+        assert_eq!(store.load().unwrap(), None);
+        // Clearing again, with nothing stored, is fine.
+        store.clear().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_store_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path("private");
+        // A file left over from elsewhere is tightened when saved over.
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let store = FileTokenStore::new(&path);
+        store.save(&tokens()).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        store.clear().unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn memory_store_clones_share_tokens() {
+        let store = MemoryTokenStore::new();
+        let handle = store.clone();
+        store.save(&tokens()).unwrap();
+        assert_eq!(handle.load().unwrap(), Some(tokens()));
+
+        store.clear().unwrap();
+        assert_eq!(handle.load().unwrap(), None);
+    }
+}
@@ -0,0 +1,457 @@
+//! The transport module abstracts the HTTP layer underneath ZohoClient, so that requests can be
+//! sent over the network with Reqwest or answered from memory when testing code built on this crate.
+
+use crate::errors::*;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// A single HTTP request, fully prepared for sending to the Zoho API.
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    /// HTTP method with which to make the request
+    pub method: Method,
+    /// Full URL of the endpoint, without any query string
+    pub url: String,
+    /// Headers to send, including Authorization
+    pub headers: HeaderMap,
+    /// Query parameters, in the order they should be appended to the URL
+    pub query: Vec<(String, String)>,
+    /// Payload to send in the body, for requests which create or update records
+    pub body: Option<RequestBody>,
+}
+
+impl HttpRequest {
+    /// Constructor for a new HttpRequest with no headers or parameters
+    pub fn new(method: Method, url: &str) -> Self {
+        HttpRequest {
+            method,
+            url: url.to_owned(),
+            headers: HeaderMap::new(),
+            query: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Set a header on this request, replacing any existing value with the same name.
+    pub fn header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| Error::transport(&e.to_string()))?;
+        let value = HeaderValue::from_str(value).map_err(|e| Error::transport(&e.to_string()))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Append query parameters to this request
+    pub fn query<K, V>(mut self, pairs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.query
+            .extend(pairs.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Set the body of this request, replacing any existing one
+    pub fn body(mut self, body: RequestBody) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Look up the value of a query parameter on this request, if it was set.
+    pub fn query_param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// The payload of a request which creates or updates a record.
+#[derive(Clone, Debug)]
+pub enum RequestBody {
+    /// Fields sent as `application/x-www-form-urlencoded`
+    Form(Vec<(String, String)>),
+    /// Fields and files sent as `multipart/form-data`
+    Multipart {
+        /// Separator between parts, which must not appear in any of them
+        boundary: String,
+        /// Plain fields, sent before the files
+        fields: Vec<(String, String)>,
+        /// Files, each with the name of the field it is sent as
+        files: Vec<(String, Upload)>,
+    },
+}
+
+impl RequestBody {
+    /// Constructor for a multipart RequestBody, with a randomly generated boundary
+    pub fn multipart(fields: Vec<(String, String)>, files: Vec<(String, Upload)>) -> Self {
+        let boundary: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        RequestBody::Multipart {
+            boundary: format!("zohohorrorshow-{}", boundary),
+            fields,
+            files,
+        }
+    }
+
+    /// The Content-Type header to send with this body
+    pub fn content_type(&self) -> String {
+        match self {
+            RequestBody::Form(_) => "application/x-www-form-urlencoded".to_owned(),
+            RequestBody::Multipart { boundary, .. } => {
+                format!("multipart/form-data; boundary={}", boundary)
+            }
+        }
+    }
+
+    /// Look up the value of a field in this body, if it was set.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        let fields = match self {
+            RequestBody::Form(fields) => fields,
+            RequestBody::Multipart { fields, .. } => fields,
+        };
+
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Encode this body for sending
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            RequestBody::Form(fields) => url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(fields)
+                .finish()
+                .into_bytes(),
+            RequestBody::Multipart {
+                boundary,
+                fields,
+                files,
+            } => {
+                let mut bytes = Vec::new();
+                for (name, value) in fields {
+                    bytes.extend_from_slice(
+                        format!(
+                            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                            boundary,
+                            quoted(name)
+                        )
+                        .as_bytes(),
+                    );
+                    bytes.extend_from_slice(value.as_bytes());
+                    bytes.extend_from_slice(b"\r\n");
+                }
+                for (name, upload) in files {
+                    bytes.extend_from_slice(
+                        format!(
+                            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                            boundary,
+                            quoted(name),
+                            quoted(&upload.file_name),
+                            upload.content_type
+                        )
+                        .as_bytes(),
+                    );
+                    bytes.extend_from_slice(&upload.data);
+                    bytes.extend_from_slice(b"\r\n");
+                }
+                bytes.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+                bytes
+            }
+        }
+    }
+}
+
+// Keep names from breaking out of their quoted header parameter.
+fn quoted(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// A file to attach to a record, e.g. a forum post or comment.
+#[derive(Clone, Debug)]
+pub struct Upload {
+    /// Name the file is given in Zoho
+    pub file_name: String,
+    /// MIME type of the file
+    pub content_type: String,
+    /// Contents of the file
+    pub data: Vec<u8>,
+}
+
+impl Upload {
+    /// Constructor for an Upload of the given contents, typed as `application/octet-stream`
+    pub fn new(file_name: &str, data: impl Into<Vec<u8>>) -> Self {
+        Upload {
+            file_name: file_name.to_owned(),
+            content_type: "application/octet-stream".to_owned(),
+            data: data.into(),
+        }
+    }
+
+    /// Constructor for an Upload of the file at the given path, named as it is on disk
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Upload::new(&file_name, std::fs::read(path)?))
+    }
+
+    /// Set the MIME type of the file, e.g. `image/png`
+    pub fn content_type(mut self, content_type: &str) -> Self {
+        self.content_type = content_type.to_owned();
+        self
+    }
+}
+
+/// A complete HTTP response, as returned by a Transport.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    /// Status code returned by the server
+    pub status: StatusCode,
+    /// Headers returned by the server
+    pub headers: HeaderMap,
+    /// Raw body of the response
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Constructor for a new HttpResponse with the given status and body, and no headers
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        HttpResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Accessor method for the response status
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The response body, lossily decoded as UTF-8
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Parse the response body as JSON into the requested type
+    pub fn json<U: serde::de::DeserializeOwned>(&self) -> Result<U> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// A Transport is responsible for delivering an HttpRequest and returning the server's response.
+/// ZohoClient holds one and routes every request through it; implement this to intercept,
+/// record or replace traffic to the Zoho API.
+pub trait Transport: std::fmt::Debug + Send + Sync {
+    /// Deliver the request and return whatever the server responded with. Non-success status
+    /// codes are not errors at this level; only a failure to get a response at all is.
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse>;
+}
+
+/// The default Transport, which makes real requests using a blocking Reqwest client.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    /// Constructor for a ReqwestTransport with a default Reqwest client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructor for a ReqwestTransport wrapping a preconfigured Reqwest client,
+    /// e.g. one with a proxy or custom timeouts.
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method.clone(), &request.url)
+            .headers(request.headers.clone());
+        if !request.query.is_empty() {
+            builder = builder.query(&request.query);
+        }
+        if let Some(ref body) = request.body {
+            builder = builder
+                .header(CONTENT_TYPE, body.content_type())
+                .body(body.to_bytes());
+        }
+
+        let response = builder.send()?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes()?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// An in-memory Transport which answers each request with the next canned response, in the
+/// order they were added, and records every request it receives. Clones share the same queue
+/// and record, so a test can keep one handle and give another to the ZohoClient.
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: VecDeque<HttpResponse>,
+    requests: Vec<HttpRequest>,
+}
+
+impl MockTransport {
+    /// Constructor for an empty MockTransport
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response with the given status and body
+    pub fn respond(&self, status: StatusCode, body: &str) -> &Self {
+        self.respond_with(HttpResponse::new(status, body))
+    }
+
+    /// Queue a fully specified response, e.g. one with headers
+    pub fn respond_with(&self, response: HttpResponse) -> &Self {
+        self.lock().responses.push_back(response);
+        self
+    }
+
+    /// All requests received so far, oldest first
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The number of queued responses not yet returned
+    pub fn remaining(&self) -> usize {
+        self.lock().responses.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        // A panic elsewhere while holding the lock leaves the queue itself intact.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let mut state = self.lock();
+        state.requests.push(request.clone());
+        state.responses.pop_front().ok_or_else(|| {
+            Error::transport(&format!(
+                "MockTransport has no response queued for {} {}",
+                request.method, request.url
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn form_body_is_url_encoded() {
+        let body = RequestBody::Form(pairs(&[("name", "a b&c"), ("flag", "é")]));
+        assert_eq!(body.content_type(), "application/x-www-form-urlencoded");
+        assert_eq!(body.to_bytes(), b"name=a+b%26c&flag=%C3%A9");
+        assert_eq!(body.field("flag"), Some("é"));
+        assert_eq!(body.field("missing"), None);
+    }
+
+    #[test]
+    fn multipart_body_layout() {
+        let body = RequestBody::Multipart {
+            boundary: "XYZ".to_owned(),
+            fields: pairs(&[("content", "Hello")]),
+            files: vec![(
+                "uploadfile".to_owned(),
+                Upload::new("notes.txt", "data").content_type("text/plain"),
+            )],
+        };
+        assert_eq!(body.content_type(), "multipart/form-data; boundary=XYZ");
+        assert_eq!(
+            String::from_utf8(body.to_bytes()).unwrap(),
+            "--XYZ\r\n\
+             Content-Disposition: form-data; name=\"content\"\r\n\r\n\
+             Hello\r\n\
+             --XYZ\r\n\
+             Content-Disposition: form-data; name=\"uploadfile\"; filename=\"notes.txt\"\r\n\
+             Content-Type: text/plain\r\n\r\n\
+             data\r\n\
+             --XYZ--\r\n"
+        );
+    }
+
+    #[test]
+    fn multipart_escapes_names() {
+        let body = RequestBody::Multipart {
+            boundary: "XYZ".to_owned(),
+            fields: Vec::new(),
+            files: vec![(
+                "uploadfile".to_owned(),
+                Upload::new("evil\".txt\r\nX-Injected: 1", "data"),
+            )],
+        };
+        let encoded = String::from_utf8(body.to_bytes()).unwrap();
+        assert!(encoded.contains("filename=\"evil%22.txt%0D%0AX-Injected: 1\""));
+    }
+
+    #[test]
+    fn multipart_boundaries_are_random() {
+        let a = RequestBody::multipart(Vec::new(), Vec::new());
+        let b = RequestBody::multipart(Vec::new(), Vec::new());
+        assert_ne!(a.content_type(), b.content_type());
+    }
+
+    #[test]
+    fn mock_transport_replays_responses_in_order() {
+        let transport = MockTransport::new();
+        transport
+            .respond(StatusCode::OK, "first")
+            .respond(StatusCode::NOT_FOUND, "second");
+        let request = HttpRequest::new(Method::GET, "https://example.com/");
+
+        assert_eq!(transport.send(&request).unwrap().text(), "first");
+        assert_eq!(
+            transport.send(&request).unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+        assert!(transport.send(&request).is_err());
+        assert_eq!(transport.requests().len(), 3);
+        assert_eq!(transport.remaining(), 0);
+    }
+}